@@ -0,0 +1,70 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy_orbit_camera::{bookmarks::CameraBookmarks, controls::OrbitControlsPlugin, *};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((
+            OrbitCameraPlugin::default(),
+            OrbitControlsPlugin::<With<MainCamera>>::default(),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+#[derive(Component)]
+pub struct MainCamera;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // help
+    commands.spawn(TextBundle {
+        text: Text {
+            sections: vec![TextSection {
+                value: "Press Ctrl + 1-9 to store a bookmark, 1-9 to recall it".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        ..default()
+    });
+    // circular base
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Circle::new(4.0)),
+        material: materials.add(Color::WHITE),
+        transform: Transform::from_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+        ..default()
+    });
+    // cube
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        material: materials.add(Color::rgb_u8(124, 144, 255)),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..default()
+    });
+    // light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn((
+        OrbitCamera {
+            radius: 6.0,
+            delta_pitch: PI / 8.0,
+            ..Default::default()
+        },
+        Camera3dBundle::default(),
+        CameraBookmarks::default(),
+        MainCamera,
+    ));
+}
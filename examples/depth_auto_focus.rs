@@ -0,0 +1,63 @@
+use bevy::{core_pipeline::prepass::DepthPrepass, prelude::*};
+use bevy_orbit_camera::{
+    controls::{OrbitControlsConfig, OrbitControlsPlugin, ScreenCenterHit},
+    depth_focus::{DepthAutoFocus, DepthAutoFocusPlugin},
+    *,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((
+            OrbitCameraPlugin::default(),
+            OrbitControlsPlugin::<With<MainCamera>>::default(),
+            DepthAutoFocusPlugin,
+        ))
+        .insert_resource(OrbitControlsConfig {
+            auto_focus_screen_center: true,
+            ..default()
+        })
+        .add_systems(Startup, setup)
+        .run();
+}
+
+#[derive(Component)]
+pub struct MainCamera;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // a row of cubes at different depths to orbit around
+    for i in 0..5 {
+        commands.spawn(PbrBundle {
+            mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+            material: materials.add(Color::rgb_u8(124, 144, 255)),
+            transform: Transform::from_xyz(2.0 * i as f32 - 4.0, 0.5, -2.0 * i as f32),
+            ..default()
+        });
+    }
+    // light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn((
+        Camera3dBundle::default(),
+        OrbitCamera {
+            radius: 10.0,
+            ..default()
+        },
+        // the depth of the prepass is read at the screen center when a rotate drag starts
+        DepthPrepass,
+        DepthAutoFocus::default(),
+        ScreenCenterHit::default(),
+        MainCamera,
+    ));
+}
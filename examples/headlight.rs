@@ -0,0 +1,62 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy_orbit_camera::{controls::OrbitControlsPlugin, follow::CameraHeadlight, *};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((
+            OrbitCameraPlugin::default(),
+            OrbitControlsPlugin::<With<MainCamera>>::default(),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+#[derive(Component)]
+pub struct MainCamera;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // circular base
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Circle::new(4.0)),
+        material: materials.add(Color::WHITE),
+        transform: Transform::from_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+        ..default()
+    });
+    // cube
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        material: materials.add(Color::rgb_u8(124, 144, 255)),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..default()
+    });
+    // camera
+    let camera = commands
+        .spawn((
+            OrbitCamera {
+                radius: 6.0,
+                delta_pitch: PI / 8.0,
+                ..Default::default()
+            },
+            Camera3dBundle::default(),
+            MainCamera,
+        ))
+        .id();
+    // headlight
+    commands.spawn((
+        DirectionalLightBundle {
+            directional_light: DirectionalLight {
+                shadows_enabled: true,
+                ..default()
+            },
+            ..default()
+        },
+        CameraHeadlight::new(camera),
+    ));
+}
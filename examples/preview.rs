@@ -0,0 +1,131 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        view::RenderLayers,
+    },
+};
+use bevy_orbit_camera::{auto_rotate::AutoRotate, controls::OrbitControlsPlugin, *};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((
+            OrbitCameraPlugin::default(),
+            // the preview camera doesn't have the marker, so it ignores input
+            OrbitControlsPlugin::<With<MainCamera>>::default(),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+#[derive(Component)]
+pub struct MainCamera;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let size = Extent3d {
+        width: 256,
+        height: 256,
+        ..default()
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: None,
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..default()
+    };
+    image.resize(size);
+    let image_handle = images.add(image);
+    let preview_layer = RenderLayers::layer(1);
+
+    // preview image
+    commands.spawn(ImageBundle {
+        image: UiImage::new(image_handle.clone()),
+        style: Style {
+            width: Val::Px(256.0),
+            height: Val::Px(256.0),
+            ..default()
+        },
+        ..default()
+    });
+    // circular base
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Circle::new(4.0)),
+        material: materials.add(Color::WHITE),
+        transform: Transform::from_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+        ..default()
+    });
+    // cube
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        material: materials.add(Color::rgb_u8(124, 144, 255)),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..default()
+    });
+    // preview object, only visible to the preview camera
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Torus::default()),
+            material: materials.add(Color::rgb_u8(255, 144, 124)),
+            ..default()
+        },
+        preview_layer,
+    ));
+    // light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+    // camera
+    commands.spawn((
+        OrbitCamera {
+            radius: 6.0,
+            delta_pitch: PI / 8.0,
+            ..Default::default()
+        },
+        Camera3dBundle::default(),
+        MainCamera,
+    ));
+    // preview camera
+    commands.spawn((
+        OrbitCamera {
+            radius: 3.0,
+            delta_pitch: PI / 6.0,
+            ..Default::default()
+        },
+        Camera3dBundle {
+            camera: Camera {
+                order: -1,
+                target: RenderTarget::Image(image_handle),
+                clear_color: ClearColorConfig::Custom(Color::WHITE),
+                ..default()
+            },
+            ..default()
+        },
+        AutoRotate::new(PI / 4.0),
+        preview_layer,
+    ));
+}
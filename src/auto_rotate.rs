@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+use crate::{
+    time::{OrbitTime, TimeSourceOverride},
+    OrbitCamera,
+};
+
+/// Component that continuously orbits an `OrbitCamera` around its up axis, e.g. for turntable previews.
+///
+/// Works without any input or window, so it can drive cameras rendering to an image.
+#[derive(Debug, Clone, Component)]
+pub struct AutoRotate {
+    /// The yaw speed in radians per second.
+    pub speed: f32,
+}
+
+impl AutoRotate {
+    pub fn new(speed: f32) -> Self {
+        Self { speed }
+    }
+}
+
+pub(crate) fn auto_rotate(
+    time: OrbitTime,
+    mut camera_q: Query<(&mut OrbitCamera, &AutoRotate, Option<&TimeSourceOverride>)>,
+) {
+    for (mut property, auto_rotate, source_override) in camera_q.iter_mut() {
+        property.yaw(auto_rotate.speed * time.delta_seconds_for(source_override));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::camera::RenderTarget;
+
+    use crate::{
+        test_util::{controls_app, next_frame, FRAME_TIME},
+        OrbitAngles,
+    };
+
+    #[test]
+    fn render_target_camera_auto_rotates_headless() {
+        // the controls run too, without a window or pointer input
+        let mut app = controls_app();
+        let camera = app
+            .world
+            .spawn((
+                OrbitCamera::new(Vec3::ZERO, 5.0),
+                Transform::default(),
+                Projection::default(),
+                Camera {
+                    target: RenderTarget::Image(Handle::default()),
+                    ..default()
+                },
+                AutoRotate::new(1.0),
+                OrbitAngles::default(),
+            ))
+            .id();
+        // the first frame has a time step of zero
+        for _ in 0..11 {
+            next_frame(&mut app);
+        }
+        let yaw = app.world.get::<OrbitAngles>(camera).unwrap().yaw;
+        assert!((yaw - 10.0 * FRAME_TIME).abs() < 1e-4);
+    }
+}
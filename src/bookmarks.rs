@@ -0,0 +1,139 @@
+use bevy::{ecs::query::QueryFilter, prelude::*, utils::HashMap};
+
+use crate::{
+    controls::OrbitControlsConfig, pose::OrbitCameraPose, transition::CameraTransition,
+    FreezeTransform, OrbitCamera,
+};
+
+/// Component storing camera poses in numbered slots.
+///
+/// Slots are bound to keys by [`OrbitControlsConfig::bookmark_keys`]. Pressing a key recalls the pose,
+/// holding [`OrbitControlsConfig::bookmark_store_modifier`] while pressing it stores the current pose.
+#[derive(Debug, Clone, Component, Default)]
+pub struct CameraBookmarks {
+    pub slots: HashMap<usize, OrbitCameraPose>,
+}
+
+impl CameraBookmarks {
+    pub fn store(&mut self, slot: usize, pose: OrbitCameraPose) {
+        self.slots.insert(slot, pose);
+    }
+
+    pub fn get(&self, slot: usize) -> Option<&OrbitCameraPose> {
+        self.slots.get(&slot)
+    }
+}
+
+type BookmarkCameraQuery<'w, 's, Filter> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut OrbitCamera,
+        &'static mut CameraBookmarks,
+        &'static mut Transform,
+        &'static mut Projection,
+        Has<FreezeTransform>,
+    ),
+    Filter,
+>;
+
+/// System for storing and recalling bookmarked poses based on keyboard input.
+pub fn bookmark_control<Filter: QueryFilter>(
+    mut commands: Commands,
+    config: Res<OrbitControlsConfig>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut camera_q: BookmarkCameraQuery<Filter>,
+) {
+    if !config.enable {
+        return;
+    }
+    let Some(slot) = config
+        .bookmark_keys
+        .iter()
+        .position(|key| keyboard.just_pressed(*key))
+    else {
+        return;
+    };
+    let store = config
+        .bookmark_store_modifier
+        .is_some_and(|modifier| keyboard.pressed(modifier));
+    for (entity, mut property, mut bookmarks, mut transform, mut projection, frozen) in
+        camera_q.iter_mut()
+    {
+        if store {
+            bookmarks.store(slot, property.pose(&transform));
+            continue;
+        }
+        let Some(pose) = bookmarks.get(slot).copied() else {
+            continue;
+        };
+        if config.bookmark_transition_duration > 0.0 {
+            commands.entity(entity).try_insert(CameraTransition::new(
+                property.pose(&transform),
+                pose,
+                config.bookmark_transition_duration,
+            ));
+        } else if !frozen {
+            property.apply_pose(&pose, &mut transform, &mut projection);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{controls_app, next_frame, spawn_camera};
+
+    #[test]
+    fn recalling_a_bookmark_restores_the_stored_pose() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .bookmark_transition_duration = 0.0;
+        let camera = spawn_camera(
+            &mut app,
+            OrbitCamera::new(Vec3::X, 4.0).with_orbit(0.3, 0.2, 0.0),
+        );
+        app.world
+            .entity_mut(camera)
+            .insert(CameraBookmarks::default());
+        next_frame(&mut app);
+
+        let mut keyboard = app.world.resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.press(KeyCode::ControlLeft);
+        keyboard.press(KeyCode::Digit1);
+        next_frame(&mut app);
+        let mut keyboard = app.world.resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.release(KeyCode::ControlLeft);
+        keyboard.release(KeyCode::Digit1);
+        next_frame(&mut app);
+        let stored = *app
+            .world
+            .get::<CameraBookmarks>(camera)
+            .unwrap()
+            .get(0)
+            .unwrap();
+
+        let mut orbit = app.world.get_mut::<OrbitCamera>(camera).unwrap();
+        orbit.orbit(1.0, 0.5, 0.0);
+        orbit.pan(Vec2::new(2.0, -1.0));
+        orbit.radius = 10.0;
+        next_frame(&mut app);
+        assert_ne!(
+            app.world.get::<OrbitCamera>(camera).unwrap().focus,
+            stored.focus
+        );
+
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Digit1);
+        next_frame(&mut app);
+        let orbit = app.world.get::<OrbitCamera>(camera).unwrap();
+        let transform = app.world.get::<Transform>(camera).unwrap();
+        let pose = orbit.pose(transform);
+        assert!(pose.focus.abs_diff_eq(stored.focus, 1e-5));
+        assert!((pose.radius - stored.radius).abs() < 1e-5);
+        assert!(pose.rotation.abs_diff_eq(stored.rotation, 1e-5));
+    }
+}
@@ -0,0 +1,351 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::{FreezeTransform, OrbitCamera};
+
+/// Component that keeps an `OrbitCamera` above a ground plane by limiting how far it can pitch down.
+///
+/// The allowed pitch tightens as the camera gets closer to the ground, depending on radius and focus height.
+#[derive(Debug, Clone, Component)]
+pub struct GroundClamp {
+    /// The height of the ground plane along the up axis of the camera.
+    pub ground_height: f32,
+    /// The minimum distance kept between the camera and the ground plane.
+    pub clearance: f32,
+}
+
+impl GroundClamp {
+    pub fn new(ground_height: f32) -> Self {
+        Self {
+            ground_height,
+            clearance: 0.0,
+        }
+    }
+
+    pub fn with_clearance(mut self, clearance: f32) -> Self {
+        self.clearance = clearance;
+        self
+    }
+
+    /// The minimum elevation angle of the camera above the focus for a given focus height and distance.
+    pub fn min_elevation(&self, focus_height: f32, distance: f32) -> f32 {
+        if distance <= 0.0 {
+            return 0.0;
+        }
+        let sin = (self.ground_height + self.clearance - focus_height) / distance;
+        sin.clamp(-1.0, 1.0).asin()
+    }
+}
+
+/// Rotates the camera about the focus so its elevation above the focus is at least `min_elevation`.
+pub(crate) fn clamp_elevation(
+    transform: &mut Transform,
+    focus: Vec3,
+    up: Vec3,
+    min_elevation: f32,
+) {
+    let distance = transform.translation.distance(focus);
+    let back = transform.rotation * Vec3::Z;
+    let elevation = back.dot(up).clamp(-1.0, 1.0).asin();
+    if elevation >= min_elevation {
+        return;
+    }
+    transform.rotation = with_elevation(transform.rotation, up, min_elevation);
+    transform.translation = focus + transform.rotation * Vec3::new(0.0, 0.0, distance);
+}
+
+/// Pitches a camera rotation so the camera is at `elevation` above the focus, keeping its yaw and roll.
+pub(crate) fn with_elevation(rotation: Quat, up: Vec3, elevation: f32) -> Quat {
+    let back = rotation * Vec3::Z;
+    let current = back.dot(up).clamp(-1.0, 1.0).asin();
+    // rotating the back vector about `back x up` turns it toward the up axis
+    let Some(axis) = back.cross(up).try_normalize() else {
+        return rotation;
+    };
+    Quat::from_axis_angle(axis, elevation - current) * rotation
+}
+
+pub(crate) fn ground_clamp(
+    mut camera_q: Query<(&OrbitCamera, &mut Transform, &GroundClamp), Without<FreezeTransform>>,
+) {
+    for (property, mut transform, clamp) in camera_q.iter_mut() {
+        let up = property.up_axis.unwrap_or_default().vector();
+        let distance = transform.translation.distance(property.focus);
+        let min_elevation = clamp.min_elevation(property.focus.dot(up), distance);
+        clamp_elevation(&mut transform, property.focus, up, min_elevation);
+    }
+}
+
+/// Component that keeps the focus of an `OrbitCamera` on the surface of a sphere, e.g. for a planet viewer.
+///
+/// Panning moves the focus along the surface, and the camera turns with it so its up direction follows
+/// the surface normal. Best used without `lock_up_axis`, which keeps the camera level to the world instead.
+#[derive(Debug, Clone, Component)]
+pub struct FocusSphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+impl FocusSphere {
+    pub fn new(center: Vec3, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    /// Projects a point onto the surface of the sphere.
+    pub fn project(&self, point: Vec3) -> Vec3 {
+        let normal = (point - self.center).try_normalize().unwrap_or(Vec3::Y);
+        self.center + self.radius * normal
+    }
+}
+
+pub(crate) fn focus_sphere(
+    mut camera_q: Query<(&mut OrbitCamera, &mut Transform, &FocusSphere), Without<FreezeTransform>>,
+) {
+    for (mut property, mut transform, sphere) in camera_q.iter_mut() {
+        let focus = sphere.project(property.focus);
+        let normal = (focus - sphere.center).normalize_or_zero();
+        // the pan is applied along the surface instead of the camera plane
+        let pan = transform.rotation * property.pan.extend(0.0);
+        let pan = pan - pan.dot(normal) * normal;
+        property.pan = Vec2::ZERO;
+        let rotation = match normal.cross(pan).try_normalize() {
+            Some(axis) if sphere.radius > 0.0 => {
+                Quat::from_axis_angle(axis, pan.length() / sphere.radius)
+            }
+            _ => Quat::IDENTITY,
+        };
+        property.focus = sphere.center + rotation * (focus - sphere.center);
+        transform.rotation = (rotation * transform.rotation).normalize();
+    }
+}
+
+/// Component that limits zooming out of an `OrbitCamera` to where an object around the focus still covers
+/// a minimum fraction of the smaller viewport dimension, so it never shrinks to a dot.
+///
+/// Unlike a `radius_limit`, the limit follows the size of the object and the field of view.
+#[derive(Debug, Clone, Component)]
+pub struct MinScreenFraction {
+    /// The radius of a sphere around the focus enclosing the object.
+    pub object_radius: f32,
+    /// The minimum fraction of the smaller viewport dimension covered by the object, e.g. `0.1`.
+    pub fraction: f32,
+}
+
+impl MinScreenFraction {
+    pub fn new(object_radius: f32, fraction: f32) -> Self {
+        Self {
+            object_radius,
+            fraction,
+        }
+    }
+
+    /// The largest radius at which the object covers `fraction` of the view.
+    ///
+    /// `viewport_size` is the logical size of the viewport.
+    pub fn max_radius(&self, projection: &Projection, viewport_size: Vec2) -> f32 {
+        OrbitCamera::distance_for_screen_fraction(
+            self.object_radius,
+            self.fraction,
+            projection,
+            viewport_size,
+        )
+    }
+}
+
+pub(crate) fn min_screen_fraction(
+    mut camera_q: Query<
+        (&mut OrbitCamera, &Camera, &Projection, &MinScreenFraction),
+        Without<FreezeTransform>,
+    >,
+) {
+    for (mut property, camera, projection, limit) in camera_q.iter_mut() {
+        let Some(viewport_size) = camera.logical_viewport_size() else {
+            continue;
+        };
+        let max_radius = limit.max_radius(projection, viewport_size);
+        if !max_radius.is_finite() || max_radius <= 0.0 {
+            continue;
+        }
+        if property.radius > max_radius {
+            property.radius = max_radius;
+        }
+        if let Some(target_radius) = property.state.target_radius.as_mut() {
+            *target_radius = target_radius.min(max_radius);
+        }
+    }
+}
+
+/// Validates the focus of an `OrbitCamera` after panning, e.g. to keep it over walkable ground,
+/// set as `OrbitCamera::focus_validator`.
+///
+/// The function receives the proposed focus and returns the corrected focus, which may be the same,
+/// or `None` to reject the move and keep the previous focus.
+///
+/// Every focus change, from the controls, following or restoring a pose, is validated by
+/// `OrbitCamera::update_transform`, including the previews of `OrbitCamera::pending_pose` and
+/// `OrbitCamera::view_projection`.
+#[derive(Clone)]
+pub struct FocusValidator(pub Arc<dyn Fn(Vec3) -> Option<Vec3> + Send + Sync>);
+
+impl FocusValidator {
+    pub fn new(validate: impl Fn(Vec3) -> Option<Vec3> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(validate))
+    }
+
+    /// The focus to use instead of `proposed`, falling back to `previous` if the move is rejected.
+    pub fn validate(&self, previous: Vec3, proposed: Vec3) -> Vec3 {
+        (self.0)(proposed).unwrap_or(previous)
+    }
+}
+
+impl std::fmt::Debug for FocusValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("FocusValidator").finish_non_exhaustive()
+    }
+}
+
+/// Validators are equal if they share the same function.
+impl PartialEq for FocusValidator {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::camera::ScalingMode;
+
+    use crate::{
+        test_util::{spawn_camera, test_app},
+        util::calculate_orthographic_area,
+    };
+
+    #[test]
+    fn ground_clamp_limits_the_pitch_more_the_closer_the_focus_is_to_the_ground() {
+        let clamp = GroundClamp::new(0.0);
+        let elevations: Vec<f32> = [4.0, 2.0, 1.0, 0.0]
+            .into_iter()
+            .map(|focus_height| clamp.min_elevation(focus_height, 5.0))
+            .collect();
+        assert!(elevations.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(elevations[3], 0.0);
+    }
+
+    #[test]
+    fn ground_clamp_keeps_the_camera_above_the_ground() {
+        let mut app = test_app();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::Y, 5.0));
+        app.world.entity_mut(camera).insert(GroundClamp::new(0.0));
+        app.update();
+        let mut lowest = f32::MAX;
+        for _ in 0..20 {
+            // a negative pitch delta tilts the camera up, moving it down
+            app.world
+                .get_mut::<OrbitCamera>(camera)
+                .unwrap()
+                .pitch(-0.1);
+            app.update();
+            let height = app.world.get::<Transform>(camera).unwrap().translation.y;
+            assert!(height >= -1e-4);
+            lowest = lowest.min(height);
+        }
+        assert!(lowest.abs() < 1e-4);
+    }
+
+    #[test]
+    fn panning_moves_the_focus_along_the_sphere() {
+        let mut app = test_app();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::new(0.0, 10.0, 0.0), 5.0));
+        app.world
+            .entity_mut(camera)
+            .insert(FocusSphere::new(Vec3::ZERO, 10.0));
+        app.update();
+
+        // five pans of two units along the surface turn the focus by one radian about the Z axis
+        for _ in 0..5 {
+            app.world
+                .get_mut::<OrbitCamera>(camera)
+                .unwrap()
+                .pan(Vec2::new(2.0, 0.0));
+            app.update();
+        }
+        let focus = app.world.get::<OrbitCamera>(camera).unwrap().focus;
+        assert!((focus.length() - 10.0).abs() < 1e-4);
+        assert!(focus.abs_diff_eq(Vec3::new(1.0f32.sin(), 1.0f32.cos(), 0.0) * 10.0, 1e-3));
+        // the camera turns with the surface
+        let up: Vec3 = app.world.get::<Transform>(camera).unwrap().up().into();
+        assert!(up.abs_diff_eq(focus.normalize(), 1e-4));
+    }
+
+    #[test]
+    fn the_max_radius_is_where_the_object_covers_the_min_fraction() {
+        let limit = MinScreenFraction::new(2.0, 0.1);
+        let viewport_size = Vec2::new(800.0, 600.0);
+
+        let projection = Projection::default();
+        let max_radius = limit.max_radius(&projection, viewport_size);
+        let Projection::Perspective(p) = &projection else {
+            unreachable!();
+        };
+        // the tangent of the half angle of the object relative to that of the vertical field of view
+        let covered = (2.0 / max_radius).asin().tan() / (0.5 * p.fov).tan();
+        assert!((covered - 0.1).abs() < 1e-4);
+        // farther out, the object would cover less
+        let covered = (2.0 / (1.1 * max_radius)).asin().tan() / (0.5 * p.fov).tan();
+        assert!(covered < 0.1);
+
+        let orthographic = OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical(2.0),
+            ..default()
+        };
+        let max_scale = limit.max_radius(
+            &Projection::Orthographic(orthographic.clone()),
+            viewport_size,
+        );
+        let area = calculate_orthographic_area(&orthographic, max_scale, viewport_size);
+        assert!((4.0 / area.min_element() - 0.1).abs() < 1e-5);
+    }
+
+    #[test]
+    fn the_focus_validator_snaps_the_panned_focus_to_a_grid() {
+        let mut orbit = OrbitCamera::new(Vec3::ZERO, 5.0);
+        // snaps to a unit grid, and rejects moves beyond x = 3
+        orbit.focus_validator = Some(FocusValidator::new(|focus| {
+            (focus.x <= 3.0).then_some(focus.round())
+        }));
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        orbit.update_transform(&mut transform, &mut projection);
+
+        // the camera looks down -Z, so the pan is along world X and Y
+        orbit.pan(Vec2::new(1.3, 0.4));
+        let pose = orbit.pending_pose(&transform, &projection);
+        assert_eq!(pose.focus, Vec3::new(1.0, 0.0, 0.0));
+        orbit.update_transform(&mut transform, &mut projection);
+        assert_eq!(orbit.focus, pose.focus);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.0, 0.0, 5.0), 1e-5));
+
+        orbit.pan(Vec2::new(3.0, 0.0));
+        orbit.update_transform(&mut transform, &mut projection);
+        assert_eq!(orbit.focus, Vec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn a_new_focus_validator_validates_the_current_focus() {
+        let mut app = test_app();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::new(0.4, -0.7, 0.0), 5.0));
+        app.update();
+
+        app.world
+            .get_mut::<OrbitCamera>(camera)
+            .unwrap()
+            .focus_validator = Some(FocusValidator::new(|focus| Some(focus.round())));
+        app.update();
+        let focus = app.world.get::<OrbitCamera>(camera).unwrap().focus;
+        assert_eq!(focus, Vec3::new(0.0, -1.0, 0.0));
+    }
+}
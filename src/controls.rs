@@ -1,16 +1,36 @@
-use std::{f32::consts::PI, marker::PhantomData};
+use std::{
+    f32::consts::{PI, TAU},
+    marker::PhantomData,
+    ops::RangeInclusive,
+};
 
 use bevy::{
-    ecs::query::QueryFilter,
+    ecs::{query::QueryFilter, system::SystemParam},
     input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
     prelude::*,
+    utils::{HashMap, HashSet},
+    window::PrimaryWindow,
 };
 
-use crate::{util::calculate_pan_scaling_factor, OrbitCamera, OrbitCameraSystemSet};
+use crate::{
+    bookmarks::bookmark_control,
+    pose::OrbitCameraPose,
+    response::ResponseCurves,
+    time::{OrbitTime, TimeSource, TimeSourceOverride},
+    transition::CameraTransition,
+    util::{
+        arcball_vector, calculate_orthographic_area, calculate_pan_scaling_factor, cursor_ray,
+        equivalent_zoom_radius, from_direction_with_up_axis,
+    },
+    FreezeTransform, OrbitCamera, OrbitCameraSystemSet,
+};
 
 /// A optional default control plugin for pan-orbit camera.
 ///
 /// `Filter` is a generic parameter requiring the `QueryFilter` trait, allowing the plugin to be conditionally applied to entities with specific components.
+///
+/// Cameras not matching the filter, e.g. previews rendering to an image on their own `RenderLayers`,
+/// are left untouched by the controls but still updated by `OrbitCameraPlugin`.
 pub struct OrbitControlsPlugin<Filter: QueryFilter = ()> {
     _marker: PhantomData<Filter>,
 }
@@ -26,41 +46,215 @@ impl<Filter: QueryFilter> Default for OrbitControlsPlugin<Filter> {
 impl<Filter: QueryFilter + Sync + Send + 'static> Plugin for OrbitControlsPlugin<Filter> {
     fn build(&self, app: &mut App) {
         app.init_resource::<OrbitControlsConfig>()
-            .add_systems(Update, smooth_component_init::<Filter>)
+            .init_resource::<ResponseCurves>()
+            .init_resource::<TimeSource>()
+            .init_resource::<GestureLock>()
+            .add_event::<SetControlsConfig>()
+            .add_event::<CameraClick>()
+            .add_event::<CameraSettled>()
+            .configure_sets(
+                Update,
+                OrbitControlsSystemSet
+                    .before(OrbitCameraSystemSet)
+                    .run_if(controls_enabled),
+            )
             .add_systems(
                 Update,
                 (
+                    smooth_component_init::<Filter>.before(OrbitControlsSystemSet),
+                    zoom_mode_switch::<Filter>.before(OrbitControlsSystemSet),
+                    camera_settled::<Filter>.after(OrbitControlsSystemSet),
+                    (set_controls_config, validate_config)
+                        .chain()
+                        .before(OrbitControlsSystemSet),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    gesture_lock
+                        .before(zoom_control::<Filter>)
+                        .before(movement_control::<Filter>),
                     zoom_control::<Filter>,
                     rotation_control::<Filter>,
+                    arcball_rotation_control::<Filter>,
                     movement_control::<Filter>,
-                    roll_control::<Filter>,
+                    pan_smoothing::<Filter>.after(movement_control::<Filter>),
+                    pan_inertia::<Filter>.before(movement_control::<Filter>),
+                    rotation_smoothing::<Filter>
+                        .after(rotation_control::<Filter>)
+                        .after(arcball_rotation_control::<Filter>)
+                        .after(keyboard_orbit_control::<Filter>)
+                        .after(nudge_control::<Filter>),
+                    (
+                        roll_control::<Filter>,
+                        roll_drag_control::<Filter>,
+                        keyboard_orbit_control::<Filter>,
+                        dolly_control::<Filter>,
+                        zoom_axis_control::<Filter>,
+                        page_pan_control::<Filter>,
+                        nudge_control::<Filter>,
+                        snap_yaw_control::<Filter>,
+                        reset_roll_control::<Filter>,
+                    ),
+                    auto_focus_control::<Filter>,
+                    bookmark_control::<Filter>,
+                    control_activity::<Filter>,
+                    camera_click::<Filter>,
+                    maintain_framing::<Filter>,
                 )
-                    .before(OrbitCameraSystemSet),
+                    .in_set(OrbitControlsSystemSet),
             );
     }
 }
 
+/// A SystemSet for the input handling systems of the controls.
+///
+/// The set is skipped entirely while `OrbitControlsConfig::enable` is false.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct OrbitControlsSystemSet;
+
+/// Run condition that is true while the controls are enabled.
+pub fn controls_enabled(config: Res<OrbitControlsConfig>) -> bool {
+    config.enable
+}
+
 /// Configuration for panning, rotation, and zooming controls.
 /// Includes speed settings, enable flags, and mouse button options for activating controls.
-#[derive(Debug, Clone, Resource)]
+#[derive(Debug, Clone, Resource, PartialEq)]
 pub struct OrbitControlsConfig {
     pub zoom_speed: f32,
     pub rotation_speed: f32,
     pub pan_speed: f32,
     pub roll_speed: f32,
+    /// The dolly speed in radii per second.
+    pub dolly_speed: f32,
     pub enable: bool,
     pub enable_zoom: bool,
     pub enable_rotation: bool,
     pub enable_pan: bool,
     pub enable_roll: bool,
     pub zoom_smoothness: f32,
+    /// The smoothness of panning in the range `0.0..=1.0`, `0.0` pans immediately.
+    pub pan_smoothness: f32,
+    /// The smoothness of the yaw when orbiting with the mouse and keyboard in the range `0.0..=1.0`,
+    /// `0.0` orbits immediately.
+    pub yaw_smoothness: f32,
+    /// The smoothness of the pitch when orbiting, like `yaw_smoothness`.
+    pub pitch_smoothness: f32,
+    /// Applies small motions directly and only smooths motions above these thresholds, for precise small
+    /// adjustments and smooth large ones. Set to `None` to smooth all motion.
+    pub smooth_above_threshold: Option<SmoothingThreshold>,
+    /// How the zoom and pan smoothing approach their target. `SmoothingModel::Spring` is used instead of
+    /// `zoom_smoothness` and `pan_smoothness`.
+    pub smoothing_model: SmoothingModel,
+    /// The maximum rate of zooming in natural-log units per second, e.g. `LN_2` at most doubles or halves
+    /// the radius per second. Zoom beyond it is eased in over the next frames. Set to `None` to disable.
+    pub max_zoom_speed: Option<f32>,
+    /// The maximum panning speed in world units per second, the rest of the pan is eased in over the next frames.
+    /// Set to `None` to disable.
+    pub max_pan_speed: Option<f32>,
+    /// The time in seconds over which the focus keeps gliding after a pan drag is released, losing about
+    /// two thirds of its speed each period. Pressing the pan button again stops it. Set to `0.0` to disable.
+    pub pan_inertia: f32,
+    /// The maximum orbiting speed in radians per second, the rest of the rotation is eased in over the next frames.
+    /// Set to `None` to disable.
+    pub max_rotation_speed: Option<f32>,
+    /// Whether zooming moves the camera or narrows the field of view.
+    pub zoom_mode: ZoomMode,
+    /// The range of the field of view in radians when zooming with `ZoomMode::Fov`.
+    pub fov_limit: RangeInclusive<f32>,
+    /// The time in seconds zooming suppresses panning after it starts and vice versa, for trackpads that send
+    /// both scroll and motion events during a pinch. Extended while the gesture continues. Set to `None` to disable.
+    pub gesture_lock_window: Option<f32>,
+    /// Adjusts the radius when the viewport is resized so the content keeps its vertical extent,
+    /// e.g. for orthographic cameras with `ScalingMode::WindowSize`. Perspective cameras keep it anyway,
+    /// since their field of view is vertical.
+    pub maintain_framing_on_resize: bool,
+    /// Radii the zoom settles on once scrolling stops, e.g. powers of two for technical viewers.
+    /// The radius eases to the nearest stop with the zoom smoothing. Set to `None` to disable.
+    pub radius_snap: Option<Vec<f32>>,
+    /// Which of the cameras matching the controls filter a scroll zooms.
+    pub zoom_scope: ZoomScope,
+    /// Ignores scrolling unless the cursor is within the viewport of the camera, e.g. over another window or panel.
+    pub zoom_requires_cursor_in_viewport: bool,
+    /// The zoom speed per pixel for trackpads and other devices scrolling in pixel units.
+    pub trackpad_zoom_speed: f32,
+    /// How much rapid successive scrolling with a mouse wheel speeds up zooming. Set to `0.0` to disable.
+    pub zoom_acceleration: f32,
+    /// The key to hold for zooming in finer increments. Set to `None` to disable.
+    pub fine_zoom_modifier: Option<KeyCode>,
+    /// The factor applied to `zoom_speed` while `fine_zoom_modifier` is held, usually less than `1.0`.
+    pub fine_zoom_factor: f32,
+    /// The lower bound of the orthographic scale when zooming, must be positive to keep the projection valid.
+    pub min_orthographic_scale: f32,
     /// The mouse button to trigger rotation, defaults to left mouse button. Set to `None` for always-on.
     pub rotate_button: Option<MouseButton>,
+    /// Normalizes horizontal rotation by the viewport width and vertical rotation by its height,
+    /// so a drag across the full viewport rotates the same amount regardless of the aspect ratio.
+    /// By default both are normalized by the smaller viewport dimension.
+    pub per_axis_viewport_normalization: bool,
+    /// The angle in radians the rotation snaps to while `snap_modifier` is held. Set to `None` to disable.
+    pub snap_increment: Option<f32>,
+    /// The key to hold for snapping the rotation to `snap_increment`. Set to `None` to always snap.
+    pub snap_modifier: Option<KeyCode>,
+    /// The number of directions the yaw snaps to when the rotate button is released. Set to `None` to disable.
+    pub snap_yaw_on_release: Option<u32>,
+    /// The duration in seconds of the transition when snapping the yaw on release.
+    pub snap_yaw_duration: f32,
+    /// Moves the focus to the [`ScreenCenterHit`] of the camera when a rotate drag starts,
+    /// so rotation pivots around what is in the center of the view.
+    pub auto_focus_screen_center: bool,
+    /// Rotates as if dragging a sphere centered on the camera viewport instead of orbiting by mouse motion.
+    pub arcball: bool,
     /// The mouse button to trigger zooming, defaults to mouse wheel. Set to `None` for always-on.
     pub zoom_button: Option<KeyCode>,
     /// The mouse button to trigger panning, defaults to right mouse button. Set to `None` for always-on.
     pub pan_button: Option<MouseButton>,
+    /// The key to hold for constraining panning to the axis of the initial drag motion. Set to `None` to disable.
+    pub pan_axis_lock_modifier: Option<KeyCode>,
     pub roll_button: Option<(KeyCode, KeyCode)>,
+    /// The optional modifier key and mouse button to roll the camera by dragging horizontally, where dragging across
+    /// the smaller viewport dimension rolls by `roll_speed` radians. Takes precedence over rotating and panning
+    /// with the same button while the modifier is held. Set to `None` to disable.
+    pub roll_drag_button: Option<(Option<KeyCode>, MouseButton)>,
+    /// The key to level the camera, easing the roll back to zero. Set to `None` to disable.
+    pub reset_roll_button: Option<KeyCode>,
+    /// The duration in seconds of the transition when resetting the roll. Set to `0.0` to level instantly.
+    pub reset_roll_duration: f32,
+    /// The keys to orbit left, right, up and down at `rotation_speed` radians per second,
+    /// e.g. the arrow keys. Disabled with `None` by default.
+    pub orbit_keys: Option<(KeyCode, KeyCode, KeyCode, KeyCode)>,
+    /// The keys to orbit left and right by `nudge_angle` per press. Set to `None` to disable.
+    pub nudge_yaw_keys: Option<(KeyCode, KeyCode)>,
+    /// The keys to orbit up and down by `nudge_angle` per press. Set to `None` to disable.
+    pub nudge_pitch_keys: Option<(KeyCode, KeyCode)>,
+    /// The angle in radians of a single nudge.
+    pub nudge_angle: f32,
+    /// The duration in seconds of the transition of a nudge. Set to `0.0` to orbit instantly.
+    pub nudge_transition_duration: f32,
+    /// The keys to pan left, right, up and down by a whole viewport per press, e.g. for slides. Set to `None` to disable.
+    pub page_pan_keys: Option<(KeyCode, KeyCode, KeyCode, KeyCode)>,
+    /// The keys to move the camera and focus forward and back along the view direction,
+    /// e.g. `Some((KeyCode::KeyW, KeyCode::KeyS))`. Disabled with `None` by default.
+    pub dolly_keys: Option<(KeyCode, KeyCode)>,
+    /// The keys to zoom in and out continuously while held, e.g. without a scroll wheel. Set to `None` to disable.
+    pub zoom_axis_keys: Option<(KeyCode, KeyCode)>,
+    /// The maximum zoom rate of `zoom_axis_keys` in natural-log units per second, e.g. `LN_2` doubles or halves
+    /// the radius per second.
+    pub zoom_axis_speed: f32,
+    /// The time in seconds the zoom of `zoom_axis_keys` takes to ramp up to `zoom_axis_speed` when a key is pressed,
+    /// and to come to a stop when it is released. Set to `0.0` to start and stop immediately.
+    pub zoom_axis_ramp: f32,
+    /// The distance in pixels the mouse has to travel while a button is held before rotation or panning starts.
+    /// Allows clicks to be used for other purposes, e.g. picking. Only applies to button-triggered controls.
+    pub drag_threshold: f32,
+    /// The keys bound to bookmark slots, the index in the list is the slot number.
+    pub bookmark_keys: Vec<KeyCode>,
+    /// The key to hold while pressing a bookmark key to store the current pose. Set to `None` to disable storing.
+    pub bookmark_store_modifier: Option<KeyCode>,
+    /// The duration in seconds of the transition when recalling a bookmark. Set to `0.0` to jump instantly.
+    pub bookmark_transition_duration: f32,
 }
 
 impl Default for OrbitControlsConfig {
@@ -70,6 +264,7 @@ impl Default for OrbitControlsConfig {
             rotation_speed: PI,
             pan_speed: 1.0,
             roll_speed: PI,
+            dolly_speed: 1.0,
 
             enable: true,
             enable_zoom: true,
@@ -78,35 +273,406 @@ impl Default for OrbitControlsConfig {
             enable_roll: true,
 
             zoom_smoothness: 0.75,
+            pan_smoothness: 0.0,
+            yaw_smoothness: 0.0,
+            pitch_smoothness: 0.0,
+            smooth_above_threshold: None,
+            smoothing_model: SmoothingModel::Exponential,
+            max_zoom_speed: None,
+            radius_snap: None,
+            gesture_lock_window: None,
+            maintain_framing_on_resize: false,
+            zoom_mode: ZoomMode::Dolly,
+            fov_limit: 0.05..=3.0,
+            max_pan_speed: None,
+            max_rotation_speed: None,
+            trackpad_zoom_speed: 0.01,
+            zoom_scope: ZoomScope::All,
+            zoom_requires_cursor_in_viewport: false,
+            zoom_acceleration: 0.0,
+            fine_zoom_modifier: None,
+            fine_zoom_factor: 0.1,
+            min_orthographic_scale: 1e-4,
 
             rotate_button: Some(MouseButton::Left),
+            per_axis_viewport_normalization: false,
+            snap_increment: None,
+            snap_modifier: None,
+            snap_yaw_on_release: None,
+            snap_yaw_duration: 0.25,
+            auto_focus_screen_center: false,
+            arcball: false,
             zoom_button: None,
             pan_button: Some(MouseButton::Right),
+            pan_axis_lock_modifier: None,
+            pan_inertia: 0.0,
             roll_button: Some((KeyCode::KeyQ, KeyCode::KeyE)),
+            roll_drag_button: None,
+            reset_roll_button: None,
+            reset_roll_duration: 0.25,
+            nudge_yaw_keys: None,
+            nudge_pitch_keys: None,
+            nudge_angle: PI / 12.0,
+            nudge_transition_duration: 0.0,
+            dolly_keys: None,
+            zoom_axis_keys: None,
+            zoom_axis_speed: 1.0,
+            zoom_axis_ramp: 0.3,
+            page_pan_keys: None,
+            orbit_keys: None,
+
+            drag_threshold: 0.0,
+
+            bookmark_keys: vec![
+                KeyCode::Digit1,
+                KeyCode::Digit2,
+                KeyCode::Digit3,
+                KeyCode::Digit4,
+                KeyCode::Digit5,
+                KeyCode::Digit6,
+                KeyCode::Digit7,
+                KeyCode::Digit8,
+                KeyCode::Digit9,
+            ],
+            bookmark_store_modifier: Some(KeyCode::ControlLeft),
+            bookmark_transition_duration: 0.5,
+        }
+    }
+}
+
+impl OrbitControlsConfig {
+    /// Sets both `yaw_smoothness` and `pitch_smoothness`.
+    pub fn set_rotation_smoothness(&mut self, smoothness: f32) {
+        self.yaw_smoothness = smoothness;
+        self.pitch_smoothness = smoothness;
+    }
+
+    /// Corrects invalid values, e.g. negative speeds, returning whether anything was changed.
+    // `clamp` would let NaN through, while `max` and `min` map it to the bound
+    #[allow(clippy::manual_clamp)]
+    pub fn validate(&mut self) -> bool {
+        // tracked per value instead of comparing the whole config, which never equals itself with a NaN in it
+        let mut corrected = false;
+        for speed in [
+            &mut self.zoom_speed,
+            &mut self.rotation_speed,
+            &mut self.pan_speed,
+            &mut self.roll_speed,
+            &mut self.dolly_speed,
+            &mut self.trackpad_zoom_speed,
+            &mut self.zoom_acceleration,
+            &mut self.fine_zoom_factor,
+            &mut self.drag_threshold,
+            &mut self.pan_inertia,
+            &mut self.zoom_axis_speed,
+            &mut self.zoom_axis_ramp,
+            &mut self.bookmark_transition_duration,
+            &mut self.nudge_transition_duration,
+            &mut self.snap_yaw_duration,
+            &mut self.reset_roll_duration,
+        ] {
+            // `max` maps NaN to the bound
+            correct(speed, |speed| speed.max(0.0), &mut corrected);
+        }
+        for smoothness in [
+            &mut self.zoom_smoothness,
+            &mut self.pan_smoothness,
+            &mut self.yaw_smoothness,
+            &mut self.pitch_smoothness,
+        ] {
+            correct(
+                smoothness,
+                |smoothness| smoothness.max(0.0).min(1.0),
+                &mut corrected,
+            );
+        }
+        correct(
+            &mut self.min_orthographic_scale,
+            |scale| scale.max(f32::EPSILON),
+            &mut corrected,
+        );
+        correct(
+            &mut self.nudge_angle,
+            |angle| if angle.is_finite() { angle } else { PI / 12.0 },
+            &mut corrected,
+        );
+        // NaN bounds map to the widest valid field of view, an inverted range is reordered
+        let (mut fov_start, mut fov_end) = (*self.fov_limit.start(), *self.fov_limit.end());
+        let valid_start = fov_start.max(f32::EPSILON);
+        let valid_end = fov_end.min(PI - f32::EPSILON);
+        correct(
+            &mut fov_start,
+            |_| valid_start.min(valid_end),
+            &mut corrected,
+        );
+        correct(&mut fov_end, |_| valid_start.max(valid_end), &mut corrected);
+        self.fov_limit = fov_start..=fov_end;
+        if let SmoothingModel::Spring { stiffness, damping } = &mut self.smoothing_model {
+            correct(stiffness, |stiffness| stiffness.max(0.0), &mut corrected);
+            correct(damping, |damping| damping.max(0.0), &mut corrected);
+        }
+        let thresholds = self
+            .smooth_above_threshold
+            .iter_mut()
+            .flat_map(|threshold| {
+                [
+                    &mut threshold.rotation,
+                    &mut threshold.pan,
+                    &mut threshold.zoom,
+                ]
+            });
+        let optional = [
+            &mut self.max_zoom_speed,
+            &mut self.max_pan_speed,
+            &mut self.max_rotation_speed,
+            &mut self.gesture_lock_window,
+            &mut self.snap_increment,
+        ]
+        .into_iter()
+        .flatten();
+        for value in thresholds.chain(optional) {
+            correct(value, |value| value.max(0.0), &mut corrected);
+        }
+        if let Some(stops) = self.radius_snap.as_mut() {
+            let len = stops.len();
+            stops.retain(|stop| stop.is_finite() && *stop > 0.0);
+            corrected |= stops.len() != len;
+        }
+        corrected
+    }
+}
+
+/// Replaces `value` with `valid(value)`, setting `corrected` if that changed it.
+fn correct(value: &mut f32, valid: impl FnOnce(f32) -> f32, corrected: &mut bool) {
+    let valid = valid(*value);
+    // NaN never equals the valid value, which never is NaN
+    if *value != valid {
+        *value = valid;
+        *corrected = true;
+    }
+}
+
+/// System that keeps the config consistent when it is edited at runtime, e.g. from an inspector.
+pub fn validate_config(mut config: ResMut<OrbitControlsConfig>) {
+    if !config.is_changed() {
+        return;
+    }
+    let mut validated = config.clone();
+    if validated.validate() {
+        warn!("Invalid OrbitControlsConfig values have been corrected: {validated:?}");
+        *config = validated;
+    }
+}
+
+/// Per-frame motion below which the input is applied directly, see `OrbitControlsConfig::smooth_above_threshold`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmoothingThreshold {
+    /// The orbit angle in radians.
+    pub rotation: f32,
+    /// The pan distance in world units.
+    pub pan: f32,
+    /// The natural logarithm of the zoom factor, e.g. `0.1` for roughly 10%.
+    pub zoom: f32,
+}
+
+/// How the zoom and pan smoothing approach their target, see `OrbitControlsConfig::smoothing_model`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SmoothingModel {
+    /// Covers a fixed fraction of the remaining distance per frame, which never overshoots.
+    #[default]
+    Exponential,
+    /// Pulls toward the target like a damped spring, which can overshoot and bounce back.
+    /// A `damping` of `2.0 * stiffness.sqrt()` is critically damped and arrives without overshooting,
+    /// less damping bounces.
+    Spring { stiffness: f32, damping: f32 },
+}
+
+/// Advances a damped spring pulling toward a target `remaining` away by one time step,
+/// returning the distance moved.
+fn spring_step<T>(
+    remaining: T,
+    velocity: &mut T,
+    stiffness: f32,
+    damping: f32,
+    delta_seconds: f32,
+) -> T
+where
+    T: Copy
+        + std::ops::Add<Output = T>
+        + std::ops::Sub<Output = T>
+        + std::ops::Mul<f32, Output = T>,
+{
+    // semi-implicit euler, which stays stable for the usual stiffness at frame rate time steps
+    *velocity = *velocity + (remaining * stiffness - *velocity * damping) * delta_seconds;
+    *velocity * delta_seconds
+}
+
+/// How zooming changes the view of perspective cameras.
+///
+/// Orthographic cameras always zoom by changing their scale.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ZoomMode {
+    /// Moves the camera toward the focus by changing the radius.
+    #[default]
+    Dolly,
+    /// Narrows the field of view at a constant radius, without changing the perspective.
+    /// Radii set with `OrbitCamera::set_radius_smoothed` are converted to the field of view with the same framing.
+    Fov,
+}
+
+/// The cameras a scroll zooms, see `OrbitControlsConfig::zoom_scope`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ZoomScope {
+    /// All cameras zoom by the same factor.
+    #[default]
+    All,
+    /// Only cameras whose viewport contains the cursor.
+    Hovered,
+    /// Only the active camera with the lowest `Camera::order`, usually the main view.
+    Primary,
+}
+
+/// Event replacing the whole `OrbitControlsConfig`, applied after correcting invalid values with
+/// [`OrbitControlsConfig::validate`]. Also works while the controls are disabled, e.g. to enable them.
+#[derive(Debug, Clone, Event)]
+pub struct SetControlsConfig(pub OrbitControlsConfig);
+
+/// System applying the last [`SetControlsConfig`] event of the frame.
+pub fn set_controls_config(
+    mut config: ResMut<OrbitControlsConfig>,
+    mut events: EventReader<SetControlsConfig>,
+) {
+    if let Some(SetControlsConfig(new_config)) = events.read().last() {
+        let mut new_config = new_config.clone();
+        if new_config.validate() {
+            warn!("Invalid values of SetControlsConfig have been corrected: {new_config:?}");
         }
+        *config = new_config;
     }
 }
 
 #[derive(Component)]
-pub struct TargetZoom(f32);
+pub struct TargetZoom {
+    /// The remaining zoom factor.
+    remaining: f32,
+    /// The velocity of `SmoothingModel::Spring` in natural-log units per second.
+    spring_velocity: f32,
+}
+
+/// The remaining pan of the pan smoothing, in the camera plane.
+#[derive(Component, Default)]
+pub struct TargetPan {
+    remaining: Vec2,
+    /// The focus expected after the pan of this frame, to detect external changes.
+    expected_focus: Option<Vec3>,
+    /// The pan velocity of the last drag frame in world units per second, for `pan_inertia`.
+    velocity: Vec2,
+    /// The velocity of `SmoothingModel::Spring` in world units per second.
+    spring_velocity: Vec2,
+}
+
+/// Component for supplying the world point at the center of the camera view, e.g. from a raycast or depth read.
+///
+/// Used by `OrbitControlsConfig::auto_focus_screen_center`, `None` if nothing is hit.
+#[derive(Debug, Clone, Copy, Component, Default)]
+pub struct ScreenCenterHit(pub Option<Vec3>);
+
+/// The remaining yaw and pitch of the rotation smoothing.
+#[derive(Component, Default)]
+pub struct TargetRotation(Vec2);
+
+/// Component reporting which controls are in use on a camera this frame, e.g. to hide UI hints while dragging.
+///
+/// Inserted alongside the smoothing components and updated every frame the controls run.
+#[derive(Debug, Clone, Copy, Component, Default, PartialEq, Eq)]
+pub struct ControlActivity {
+    pub rotating: bool,
+    pub panning: bool,
+    pub zooming: bool,
+    pub rolling: bool,
+}
+
+impl ControlActivity {
+    /// Whether any control is in use.
+    pub fn any(&self) -> bool {
+        self.rotating || self.panning || self.zooming || self.rolling
+    }
+}
 
+/// System inserting the smoothing components into new cameras.
+///
+/// Runs before the controls, so the components are there on the spawn frame.
 pub fn smooth_component_init<Filter: QueryFilter>(
     mut commands: Commands,
     mut camera_q: Query<Entity, (Added<OrbitCamera>, Filter)>,
 ) {
     for entity in camera_q.iter_mut() {
-        commands.entity(entity).try_insert(TargetZoom(1.0));
+        commands.entity(entity).try_insert((
+            TargetZoom {
+                remaining: 1.0,
+                spring_velocity: 0.0,
+            },
+            TargetPan::default(),
+            TargetRotation::default(),
+            ControlActivity::default(),
+        ));
     }
 }
 
+/// The rate at which the recent scroll amount used for zoom acceleration decays, per second.
+const ZOOM_ACCELERATION_DECAY: f32 = 5.0;
+
+/// The smallest factor a single accelerated wheel step scales the radius by.
+const MIN_ACCELERATED_ZOOM_FACTOR: f32 = 0.1;
+
+/// System parameter with the config and input read by `zoom_control`.
+#[derive(SystemParam)]
+pub struct ZoomInput<'w, 's> {
+    config: Res<'w, OrbitControlsConfig>,
+    curves: Res<'w, ResponseCurves>,
+    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    gesture_lock: Res<'w, GestureLock>,
+    window_q: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    scroll_events: EventReader<'w, 's, MouseWheel>,
+}
+
+type ZoomCameraQuery<'w, 's, Filter> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut OrbitCamera,
+        Option<&'static mut TargetZoom>,
+        &'static mut Projection,
+        &'static Camera,
+        Option<&'static TimeSourceOverride>,
+    ),
+    Filter,
+>;
+
 /// System for controlling camera zoom based on mouse wheel input.
 pub fn zoom_control<Filter: QueryFilter>(
-    config: Res<OrbitControlsConfig>,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut camera_q: Query<(&mut OrbitCamera, Option<&mut TargetZoom>), Filter>,
-    mut scroll_events: EventReader<MouseWheel>,
+    time: OrbitTime,
+    input: ZoomInput,
+    mut camera_q: ZoomCameraQuery<Filter>,
+    mut recent_scroll: Local<f32>,
+    mut pending_snap: Local<HashSet<Entity>>,
+    mut was_orthographic: Local<HashMap<Entity, bool>>,
+    mut removed_cameras: RemovedComponents<OrbitCamera>,
 ) {
-    if !config.enable || !config.enable_zoom {
+    let ZoomInput {
+        config,
+        curves,
+        keyboard,
+        gesture_lock,
+        window_q,
+        mut scroll_events,
+    } = input;
+    for entity in removed_cameras.read() {
+        pending_snap.remove(&entity);
+        was_orthographic.remove(&entity);
+    }
+    if !config.enable || !config.enable_zoom || gesture_lock.gesture == Some(Gesture::Pan) {
         scroll_events.clear();
         return;
     }
@@ -115,105 +681,2693 @@ pub fn zoom_control<Filter: QueryFilter>(
             scroll_events.clear();
         }
     }
+    *recent_scroll *= (-ZOOM_ACCELERATION_DECAY * time.delta_seconds()).exp();
+    let fine_factor = if config
+        .fine_zoom_modifier
+        .is_some_and(|modifier| keyboard.pressed(modifier))
+    {
+        config.fine_zoom_factor
+    } else {
+        1.0
+    };
     let mut zoom_factor = 1.0;
+    let mut pixel_scroll = 0.0;
     for event in scroll_events.read() {
-        let scroll_value = match event.unit {
-            MouseScrollUnit::Line => event.y,
-            MouseScrollUnit::Pixel => 0.005 * event.y,
-        };
-        zoom_factor *= 1.0 - scroll_value * config.zoom_speed;
+        match event.unit {
+            MouseScrollUnit::Line => {
+                let scroll_value = curves.zoom.apply(event.y);
+                *recent_scroll += scroll_value.abs();
+                let step = scroll_value * config.zoom_speed * fine_factor;
+                zoom_factor *= if config.zoom_acceleration == 0.0 {
+                    1.0 - step
+                } else {
+                    let acceleration = 1.0 + config.zoom_acceleration * *recent_scroll;
+                    // a fast burst with a large acceleration could otherwise zero or invert the radius
+                    (1.0 - step * acceleration).max(MIN_ACCELERATED_ZOOM_FACTOR)
+                };
+            }
+            // trackpads send many small events, which are coalesced and applied once per frame
+            MouseScrollUnit::Pixel => pixel_scroll += event.y,
+        }
+    }
+    if pixel_scroll != 0.0 {
+        let scroll_value = curves.zoom.apply(pixel_scroll);
+        // exponential, since a frame of coalesced pixels can exceed a full zoom step
+        zoom_factor *= (-scroll_value * config.trackpad_zoom_speed * fine_factor).exp();
     }
-    for (mut property, target_zoom) in camera_q.iter_mut() {
+    let cursor_pos = window_q
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+    let primary_camera = camera_q
+        .iter()
+        .filter(|(.., camera, _)| camera.is_active)
+        .min_by_key(|(.., camera, _)| camera.order)
+        .map(|(entity, ..)| entity);
+    for (entity, mut property, mut target_zoom, mut projection, camera, source_override) in
+        camera_q.iter_mut()
+    {
+        // input of the spawn frame would be applied before the camera is initialized, popping it
+        if property.is_added() {
+            continue;
+        }
+        // the remaining zoom was meant for the previous projection, so it's dropped on a switch
+        let orthographic = matches!(*projection, Projection::Orthographic(_));
+        if was_orthographic
+            .insert(entity, orthographic)
+            .is_some_and(|was_orthographic| was_orthographic != orthographic)
+        {
+            if let Some(target_zoom) = target_zoom.as_mut() {
+                target_zoom.remaining = 1.0;
+                target_zoom.spring_velocity = 0.0;
+            }
+            pending_snap.remove(&entity);
+        }
+        let cursor_in_viewport = cursor_pos.is_some_and(|cursor_pos| {
+            camera
+                .logical_viewport_rect()
+                .is_some_and(|viewport| viewport.contains(cursor_pos))
+        });
+        let in_scope = match config.zoom_scope {
+            ZoomScope::All => true,
+            ZoomScope::Hovered => cursor_in_viewport,
+            ZoomScope::Primary => primary_camera == Some(entity),
+        };
+        // scrolling elsewhere is ignored, but the zoom smoothing continues
+        let zoom_factor =
+            if !in_scope || (config.zoom_requires_cursor_in_viewport && !cursor_in_viewport) {
+                1.0
+            } else {
+                zoom_factor
+            };
+        // a radius set with `OrbitCamera::set_radius_smoothed` replaces the remaining zoom
+        let mut target_factor = property
+            .state
+            .target_radius
+            .take()
+            .map(|radius| radius / property.radius);
+        if zoom_factor != 1.0 {
+            pending_snap.insert(entity);
+        } else if let (Some(stops), ZoomMode::Dolly) = (&config.radius_snap, config.zoom_mode) {
+            let converged = target_zoom.as_ref().is_none_or(|target_zoom| {
+                (target_zoom.remaining - 1.0).abs() < 1e-3
+                    && target_zoom.spring_velocity.abs() < 1e-3
+            });
+            if converged && target_factor.is_none() && pending_snap.remove(&entity) {
+                target_factor = nearest_radius_stop(stops, property.radius)
+                    .map(|radius| radius / property.radius);
+            }
+        }
+        // zoom below the threshold skips the smoothing
+        let direct_factor = match config.smooth_above_threshold {
+            Some(threshold) if zoom_factor.max(f32::EPSILON).ln().abs() <= threshold.zoom => {
+                zoom_factor
+            }
+            _ => 1.0,
+        };
         let factor = if let Some(mut target_zoom) = target_zoom {
-            target_zoom.0 *= zoom_factor;
-            let smoothness = config.zoom_smoothness;
-            let zoom_factor = f32::lerp(1.0, target_zoom.0, 1.0 - smoothness);
-            target_zoom.0 /= zoom_factor;
-            zoom_factor
+            if let Some(target_factor) = target_factor {
+                target_zoom.remaining = target_factor;
+            }
+            target_zoom.remaining *= zoom_factor / direct_factor;
+            let delta_seconds = time.delta_seconds_for(source_override);
+            let mut zoom_factor = match config.smoothing_model {
+                SmoothingModel::Exponential => {
+                    zoom_smoothing_step(target_zoom.remaining, config.zoom_smoothness)
+                }
+                SmoothingModel::Spring { stiffness, damping } => spring_step(
+                    target_zoom.remaining.max(f32::EPSILON).ln(),
+                    &mut target_zoom.spring_velocity,
+                    stiffness,
+                    damping,
+                    delta_seconds,
+                )
+                .exp(),
+            };
+            if let Some(max_speed) = config.max_zoom_speed {
+                let max_step = max_speed * delta_seconds;
+                zoom_factor = zoom_factor
+                    .max(f32::EPSILON)
+                    .ln()
+                    .clamp(-max_step, max_step)
+                    .exp();
+            }
+            target_zoom.remaining /= zoom_factor;
+            zoom_factor * direct_factor
         } else {
-            zoom_factor
+            target_factor.unwrap_or(1.0) * zoom_factor
         };
-        property.zoom(factor);
+        apply_zoom(&config, &mut property, &mut projection, factor);
     }
 }
 
-pub fn rotation_control<Filter: QueryFilter>(
+/// Zooms by a factor of the radius according to the `zoom_mode` of the config.
+fn apply_zoom(
+    config: &OrbitControlsConfig,
+    property: &mut OrbitCamera,
+    projection: &mut Projection,
+    factor: f32,
+) {
+    match projection {
+        Projection::Perspective(p) if config.zoom_mode == ZoomMode::Fov => {
+            // scaling the tangent of the half angle frames like scaling the radius
+            let fov = 2.0 * (factor * (0.5 * p.fov).tan()).atan();
+            // `clamp` panics on an inverted or NaN range
+            p.fov = fov
+                .max(*config.fov_limit.start())
+                .min(*config.fov_limit.end());
+        }
+        Projection::Perspective(_) => property.zoom(factor),
+        Projection::Orthographic(_) => {
+            property.zoom(factor);
+            // the radius is used as orthographic scale, which inverts the projection at zero
+            property.radius = property.radius.max(config.min_orthographic_scale);
+        }
+    }
+}
+
+/// System keeping the framing when `OrbitControlsConfig::zoom_mode` is switched.
+///
+/// Switching to `ZoomMode::Fov` remembers the field of view of each camera. Switching back to `ZoomMode::Dolly`
+/// restores it, or the default one for cameras added in between, and moves the camera to the radius
+/// at which the focus appears at the same size, so there's no visual jump.
+pub fn zoom_mode_switch<Filter: QueryFilter>(
+    config: Res<OrbitControlsConfig>,
+    mut camera_q: Query<(Entity, &mut OrbitCamera, &mut Projection), Filter>,
+    mut previous_mode: Local<ZoomMode>,
+    mut dolly_fovs: Local<HashMap<Entity, f32>>,
+) {
+    if config.zoom_mode == *previous_mode {
+        return;
+    }
+    *previous_mode = config.zoom_mode;
+    if config.zoom_mode == ZoomMode::Fov {
+        dolly_fovs.clear();
+        for (entity, _, projection) in camera_q.iter() {
+            if let Projection::Perspective(p) = projection {
+                dolly_fovs.insert(entity, p.fov);
+            }
+        }
+        return;
+    }
+    let default_fov = PerspectiveProjection::default().fov;
+    for (entity, mut property, mut projection) in camera_q.iter_mut() {
+        if let Projection::Perspective(p) = projection.as_mut() {
+            let fov = dolly_fovs.get(&entity).copied().unwrap_or(default_fov);
+            property.radius = equivalent_zoom_radius(property.radius, p.fov, fov);
+            p.fov = fov;
+        }
+    }
+    dolly_fovs.clear();
+}
+
+/// A gesture that can suppress the others, see `OrbitControlsConfig::gesture_lock_window`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gesture {
+    Zoom,
+    Pan,
+}
+
+/// Resource holding the gesture locked by `OrbitControlsConfig::gesture_lock_window`.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct GestureLock {
+    pub gesture: Option<Gesture>,
+    remaining: f32,
+}
+
+/// The distance in pixels a scrolled line counts as when comparing zoom and pan gestures.
+const SCROLL_LINE_PIXELS: f32 = 20.0;
+
+/// System locking onto the dominant of zooming and panning for `OrbitControlsConfig::gesture_lock_window`.
+pub fn gesture_lock(
+    time: OrbitTime,
     config: Res<OrbitControlsConfig>,
     mouse_input: Res<ButtonInput<MouseButton>>,
-    mut camera_q: Query<(&mut OrbitCamera, &Camera), Filter>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut lock: ResMut<GestureLock>,
+    mut scroll_events: EventReader<MouseWheel>,
     mut mouse_motion_events: EventReader<MouseMotion>,
 ) {
-    if !config.enable || !config.enable_rotation {
+    let scroll = scroll_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y.abs() * SCROLL_LINE_PIXELS,
+            MouseScrollUnit::Pixel => event.y.abs(),
+        })
+        .sum::<f32>();
+    let motion = mouse_motion_events
+        .read()
+        .map(|event| event.delta.length())
+        .sum::<f32>();
+    let Some(window) = config.gesture_lock_window else {
+        lock.gesture = None;
+        return;
+    };
+    let zooming = config.enable_zoom
+        && config
+            .zoom_button
+            .is_none_or(|button| keyboard.pressed(button))
+        && scroll > 0.0;
+    let panning = config.enable_pan
+        && config
+            .pan_button
+            .is_none_or(|button| mouse_input.pressed(button))
+        && motion > 0.0;
+    let active = match lock.gesture {
+        Some(Gesture::Zoom) => zooming,
+        Some(Gesture::Pan) => panning,
+        None if zooming && (!panning || scroll >= motion) => {
+            lock.gesture = Some(Gesture::Zoom);
+            true
+        }
+        None if panning => {
+            lock.gesture = Some(Gesture::Pan);
+            true
+        }
+        None => false,
+    };
+    if active {
+        lock.remaining = window;
+    } else {
+        lock.remaining -= time.delta_seconds();
+        if lock.remaining <= 0.0 {
+            lock.gesture = None;
+        }
+    }
+}
+
+/// The zoom factor the zoom smoothing applies in one frame, for a remaining zoom factor of `remaining`.
+pub fn zoom_smoothing_step(remaining: f32, smoothness: f32) -> f32 {
+    f32::lerp(1.0, remaining, 1.0 - smoothness)
+}
+
+/// The frame time at which `OrbitControlsConfig::zoom_smoothness` is the fraction of the remaining zoom kept
+/// per sample of [`sample_zoom_response`].
+const ZOOM_RESPONSE_REFERENCE_FRAME_TIME: f32 = 1.0 / 60.0;
+
+/// Samples the response of the zoom smoothing to a zoom step, e.g. to plot it while tuning `zoom_smoothness`.
+///
+/// Sample `i` is the fraction of a doubling of the radius that has been applied after `(i + 1) as f32 * dt`
+/// seconds. The blend of each sample is scaled by `dt` relative to a frame at 60 frames per second, so a
+/// smaller `dt` samples the same curve more finely.
+///
+/// The response rises monotonically toward `1.0` for a smoothness below `1.0`. At `1.0` the smoothing never
+/// applies the zoom and the response stays at `0.0`.
+pub fn sample_zoom_response(smoothness: f32, dt: f32, steps: usize) -> Vec<f32> {
+    let target = 2.0_f32;
+    let kept = smoothness
+        .clamp(0.0, 1.0)
+        .powf(dt.max(0.0) / ZOOM_RESPONSE_REFERENCE_FRAME_TIME);
+    let mut remaining = target;
+    (0..steps)
+        .map(|_| {
+            remaining /= zoom_smoothing_step(remaining, kept);
+            1.0 - remaining.ln() / target.ln()
+        })
+        .collect()
+}
+
+/// Returns the stop closest to `radius` by ratio, so a stop twice as far counts the same as one half as far.
+pub fn nearest_radius_stop(stops: &[f32], radius: f32) -> Option<f32> {
+    stops
+        .iter()
+        .copied()
+        .filter(|stop| *stop > 0.0)
+        .min_by(|a, b| {
+            let distance = |stop: f32| (stop / radius).ln().abs();
+            distance(*a).total_cmp(&distance(*b))
+        })
+}
+
+/// Accumulates the dragged distance and returns whether it exceeds the threshold.
+fn exceeds_drag_threshold(drag_distance: &mut f32, delta: Vec2, threshold: f32) -> bool {
+    if *drag_distance < threshold {
+        *drag_distance += delta.length();
+    }
+    *drag_distance >= threshold
+}
+
+/// Converts a mouse delta in pixels to the yaw and pitch `rotation_control` orbits by.
+///
+/// Dragging across the smaller viewport dimension orbits by `rotation_speed` radians, which lets custom input
+/// handling reuse the feel of the built-in controls.
+pub fn pixel_delta_to_orbit(delta: Vec2, viewport_size: Vec2, rotation_speed: f32) -> Vec2 {
+    let delta_angle = Vec2::new(-delta.x, delta.y);
+    rotation_speed * delta_angle / viewport_size.min_element()
+}
+
+/// System parameter with the config and input read by the drag controls `rotation_control` and `movement_control`.
+#[derive(SystemParam)]
+pub struct DragInput<'w> {
+    config: Res<'w, OrbitControlsConfig>,
+    curves: Res<'w, ResponseCurves>,
+    mouse_input: Res<'w, ButtonInput<MouseButton>>,
+    keyboard: Res<'w, ButtonInput<KeyCode>>,
+}
+
+/// System for orbiting the camera by dragging the mouse.
+///
+/// Like all pointer-based controls, input is coalesced per frame: the `MouseMotion` events of a frame are summed
+/// and the drag threshold, response curve and snapping are applied once to the net delta. The result doesn't
+/// depend on how many events a high polling rate mouse delivers.
+pub fn rotation_control<Filter: QueryFilter>(
+    input: DragInput,
+    mut camera_q: Query<(Entity, &mut OrbitCamera, &Camera), Filter>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut drag_distance: Local<f32>,
+    mut snap_remainders: Local<HashMap<Entity, Vec2>>,
+    mut removed_cameras: RemovedComponents<OrbitCamera>,
+) {
+    let DragInput {
+        config,
+        curves,
+        mouse_input,
+        keyboard,
+    } = input;
+    // the id of a despawned camera can be reused by a new one, which starts without a remainder
+    for entity in removed_cameras.read() {
+        snap_remainders.remove(&entity);
+    }
+    if !config.enable
+        || !config.enable_rotation
+        || config.arcball
+        || roll_drag_pressed(&config, &mouse_input, &keyboard)
+    {
+        *drag_distance = 0.0;
+        snap_remainders.clear();
         mouse_motion_events.clear();
         return;
     }
     if let Some(button) = config.rotate_button {
         if !mouse_input.pressed(button) {
+            *drag_distance = 0.0;
+            snap_remainders.clear();
             mouse_motion_events.clear();
             return;
         }
     }
-    let delta_angle = mouse_motion_events
+    let mouse_motion = mouse_motion_events
         .read()
-        .map(|event| Vec2::new(-event.delta.x, event.delta.y))
+        .map(|event| event.delta)
         .sum::<Vec2>();
-    for (mut property, camera) in camera_q.iter_mut() {
+    if config.rotate_button.is_some()
+        && !exceeds_drag_threshold(&mut drag_distance, mouse_motion, config.drag_threshold)
+    {
+        return;
+    }
+    let mouse_motion = curves.rotation.apply_vec2(mouse_motion);
+    let snap_increment = config.snap_increment.filter(|_| {
+        config
+            .snap_modifier
+            .is_none_or(|modifier| keyboard.pressed(modifier))
+    });
+    if snap_increment.is_none() {
+        snap_remainders.clear();
+    }
+    for (entity, mut property, camera) in camera_q.iter_mut() {
+        if property.is_added() {
+            continue;
+        }
         if let Some(viewport_size) = camera.physical_viewport_size() {
-            let min_size = viewport_size.as_vec2().min_element();
-            let delta = config.rotation_speed * delta_angle / min_size;
+            let viewport_size = viewport_size.as_vec2();
+            let mut delta = if config.per_axis_viewport_normalization {
+                let delta_angle = Vec2::new(-mouse_motion.x, mouse_motion.y);
+                config.rotation_speed * delta_angle / viewport_size
+            } else {
+                pixel_delta_to_orbit(mouse_motion, viewport_size, config.rotation_speed)
+            };
+            if let Some(increment) = snap_increment.filter(|increment| *increment > 0.0) {
+                // only apply whole increments and keep the rest for the next frames
+                let remainder = snap_remainders.entry(entity).or_default();
+                *remainder += delta;
+                delta = (*remainder / increment).trunc() * increment;
+                *remainder -= delta;
+            }
             property.orbit(delta.x, delta.y, 0.0);
         }
     }
 }
 
-pub fn movement_control<Filter: QueryFilter>(
+/// System for arcball rotation, used instead of `rotation_control` when `OrbitControlsConfig::arcball` is set.
+///
+/// Works on the cursor position once per frame, so the rotation only depends on the net motion of the frame.
+pub fn arcball_rotation_control<Filter: QueryFilter>(
     config: Res<OrbitControlsConfig>,
     mouse_input: Res<ButtonInput<MouseButton>>,
-    mut camera_q: Query<(&mut OrbitCamera, &Camera, &Projection), Filter>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    mut camera_q: Query<(&mut OrbitCamera, &Camera), Filter>,
+    mut last_cursor_pos: Local<Option<Vec2>>,
+) {
+    let pressed = config
+        .rotate_button
+        .is_none_or(|button| mouse_input.pressed(button));
+    let cursor_pos = window_q
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+    if !config.enable || !config.enable_rotation || !config.arcball || !pressed {
+        *last_cursor_pos = None;
+        return;
+    }
+    let Some(cursor_pos) = cursor_pos else {
+        *last_cursor_pos = None;
+        return;
+    };
+    let Some(last_pos) = last_cursor_pos.replace(cursor_pos) else {
+        return;
+    };
+    for (mut property, camera) in camera_q.iter_mut() {
+        if property.is_added() {
+            continue;
+        }
+        let Some(viewport) = camera.logical_viewport_rect() else {
+            continue;
+        };
+        let from = arcball_vector(last_pos, viewport);
+        let to = arcball_vector(cursor_pos, viewport);
+        // the scene follows the cursor, so the camera rotates the opposite way
+        let (axis, angle) = Quat::from_rotation_arc(to, from).to_axis_angle();
+        let delta = config.rotation_speed / PI * angle * axis;
+        property.orbit(delta.y, -delta.x, delta.z);
+    }
+}
+
+type PanCameraQuery<'w, 's, Filter> = Query<
+    'w,
+    's,
+    (
+        &'static mut OrbitCamera,
+        &'static Camera,
+        &'static Projection,
+        Option<&'static mut TargetPan>,
+        Option<&'static TimeSourceOverride>,
+    ),
+    Filter,
+>;
+
+/// System for panning the camera by dragging the mouse, coalescing the motion of a frame like `rotation_control`.
+pub fn movement_control<Filter: QueryFilter>(
+    time: OrbitTime,
+    input: DragInput,
+    mut camera_q: PanCameraQuery<Filter>,
     mut mouse_motion_events: EventReader<MouseMotion>,
+    mut drag_distance: Local<f32>,
+    mut locked_axis: Local<Option<Vec2>>,
+    gesture_lock: Res<GestureLock>,
 ) {
-    if !config.enable || !config.enable_pan {
+    let DragInput {
+        config,
+        curves,
+        mouse_input,
+        keyboard,
+    } = input;
+    if !config.enable
+        || !config.enable_pan
+        || gesture_lock.gesture == Some(Gesture::Zoom)
+        || roll_drag_pressed(&config, &mouse_input, &keyboard)
+    {
+        *drag_distance = 0.0;
+        *locked_axis = None;
         mouse_motion_events.clear();
         return;
     }
     if let Some(button) = config.pan_button {
         if !mouse_input.pressed(button) {
+            *drag_distance = 0.0;
+            *locked_axis = None;
             mouse_motion_events.clear();
             return;
         }
     }
-    let mouse_motion = mouse_motion_events
+    let mut mouse_motion = mouse_motion_events
         .read()
         .map(|event| event.delta)
         .sum::<Vec2>();
+    if config.pan_button.is_some()
+        && !exceeds_drag_threshold(&mut drag_distance, mouse_motion, config.drag_threshold)
+    {
+        return;
+    }
+    if config
+        .pan_axis_lock_modifier
+        .is_some_and(|modifier| keyboard.pressed(modifier))
+    {
+        // lock to the axis of the first motion, as a mask zeroing the other axis
+        if locked_axis.is_none() && mouse_motion != Vec2::ZERO {
+            let abs = mouse_motion.abs();
+            *locked_axis = Some(if abs.x >= abs.y { Vec2::X } else { Vec2::Y });
+        }
+        mouse_motion *= locked_axis.unwrap_or(Vec2::ZERO);
+    } else {
+        *locked_axis = None;
+    }
+    let mouse_motion = curves.pan.apply_vec2(mouse_motion);
 
-    for (mut property, camera, projection) in camera_q.iter_mut() {
+    for (mut property, camera, projection, mut target_pan, source_override) in camera_q.iter_mut() {
+        if property.is_added() {
+            continue;
+        }
         let pan_delta = Vec2::new(-mouse_motion.x, mouse_motion.y);
         if let Some(factor) = calculate_pan_scaling_factor(camera, projection, &property) {
-            property.pan(config.pan_speed * factor * pan_delta);
+            let pan = config.pan_speed * factor * pan_delta;
+            if let Some(target_pan) = target_pan.as_mut() {
+                // kept for the glide of `pan_inertia` after the pan button is released
+                let delta_seconds = time.delta_seconds_for(source_override);
+                target_pan.velocity = if delta_seconds > 0.0 {
+                    pan / delta_seconds
+                } else {
+                    Vec2::ZERO
+                };
+            }
+            let below_threshold = config
+                .smooth_above_threshold
+                .is_some_and(|threshold| pan.length() <= threshold.pan);
+            match target_pan {
+                Some(mut target_pan)
+                    if (config.pan_smoothness > 0.0
+                        || config.max_pan_speed.is_some()
+                        || config.smoothing_model != SmoothingModel::Exponential)
+                        && !below_threshold =>
+                {
+                    target_pan.remaining += pan
+                }
+                _ => property.pan(pan),
+            }
+        }
+    }
+}
+
+/// System easing in the pan accumulated by `movement_control` when `pan_smoothness`, `max_pan_speed` or a spring
+/// `smoothing_model` is set.
+///
+/// If the focus is changed from outside, e.g. with `OrbitCamera::focus` directly, the remaining pan is dropped
+/// so the smoothing doesn't fight the change.
+pub fn pan_smoothing<Filter: QueryFilter>(
+    time: OrbitTime,
+    config: Res<OrbitControlsConfig>,
+    mut camera_q: Query<
+        (
+            &mut OrbitCamera,
+            &Transform,
+            &mut TargetPan,
+            Option<&TimeSourceOverride>,
+        ),
+        Filter,
+    >,
+) {
+    for (mut property, transform, mut target_pan, source_override) in camera_q.iter_mut() {
+        if let Some(expected_focus) = target_pan.expected_focus {
+            if property.focus.distance_squared(expected_focus) > 1e-8 {
+                target_pan.remaining = Vec2::ZERO;
+                target_pan.spring_velocity = Vec2::ZERO;
+            }
+        }
+        let delta_seconds = time.delta_seconds_for(source_override);
+        let mut pan = match config.smoothing_model {
+            SmoothingModel::Exponential => target_pan.remaining * (1.0 - config.pan_smoothness),
+            SmoothingModel::Spring { stiffness, damping } => spring_step(
+                target_pan.remaining,
+                &mut target_pan.spring_velocity,
+                stiffness,
+                damping,
+                delta_seconds,
+            ),
+        };
+        if let Some(max_speed) = config.max_pan_speed {
+            pan = pan.clamp_length_max(max_speed * delta_seconds);
+        }
+        target_pan.remaining -= pan;
+        property.pan(pan);
+        // `update_transform` moves the focus by the pending pan in the current camera plane
+        target_pan.expected_focus =
+            Some(property.focus + transform.rotation * property.pan.extend(0.0));
+    }
+}
+
+/// System letting the focus glide on after a pan drag is released when `pan_inertia` is set.
+///
+/// Runs before `movement_control`, which records the velocity of the drag while the pan button is held.
+/// The glide stops where the `pan_rect` of the camera stops the focus.
+pub fn pan_inertia<Filter: QueryFilter>(
+    time: OrbitTime,
+    config: Res<OrbitControlsConfig>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut camera_q: Query<
+        (
+            &mut OrbitCamera,
+            &mut TargetPan,
+            &Transform,
+            Option<&TimeSourceOverride>,
+        ),
+        Filter,
+    >,
+) {
+    let dragging = config
+        .pan_button
+        .is_none_or(|button| mouse_input.pressed(button));
+    for (mut property, mut target_pan, transform, source_override) in camera_q.iter_mut() {
+        if dragging || !config.enable || !config.enable_pan || config.pan_inertia <= 0.0 {
+            target_pan.velocity = Vec2::ZERO;
+            continue;
+        }
+        if target_pan.velocity == Vec2::ZERO {
+            continue;
+        }
+        let delta_seconds = time.delta_seconds_for(source_override);
+        let pan = target_pan.velocity * delta_seconds;
+        let focus = property.focus + transform.rotation * (property.pan + pan).extend(0.0);
+        if property.clamp_to_pan_rect(focus) != focus {
+            target_pan.velocity = Vec2::ZERO;
+        }
+        property.pan(pan);
+        target_pan.velocity *= (-delta_seconds / config.pan_inertia).exp();
+        if target_pan.velocity.length() < SETTLED_EPSILON {
+            target_pan.velocity = Vec2::ZERO;
+        }
+    }
+}
+
+/// System easing in the yaw and pitch of the other rotation controls when `yaw_smoothness` or `pitch_smoothness` is set,
+/// and holding back orbiting faster than `max_rotation_speed`.
+///
+/// The held back rotation is applied over the next frames, so large jumps, e.g. from a fast flick,
+/// turn into a smooth motion of limited speed.
+///
+/// Runs after all controls orbiting by yaw and pitch deltas, so their input of the same frame is always smoothed.
+pub fn rotation_smoothing<Filter: QueryFilter>(
+    time: OrbitTime,
+    config: Res<OrbitControlsConfig>,
+    mut camera_q: Query<
+        (
+            &mut OrbitCamera,
+            &mut TargetRotation,
+            Option<&TimeSourceOverride>,
+        ),
+        Filter,
+    >,
+) {
+    for (mut property, mut target_rotation, source_override) in camera_q.iter_mut() {
+        let input = Vec2::new(property.delta_yaw, property.delta_pitch);
+        let below_threshold = config
+            .smooth_above_threshold
+            .is_some_and(|threshold| input.length() <= threshold.rotation);
+        let direct = if below_threshold {
+            input
+        } else {
+            target_rotation.0 += input;
+            Vec2::ZERO
+        };
+        let smoothness = Vec2::new(config.yaw_smoothness, config.pitch_smoothness);
+        let eased = target_rotation.0 * (1.0 - smoothness);
+        target_rotation.0 -= eased;
+        let mut delta = direct + eased;
+        if let Some(max_speed) = config.max_rotation_speed {
+            let limited =
+                delta.clamp_length_max(max_speed * time.delta_seconds_for(source_override));
+            target_rotation.0 += delta - limited;
+            delta = limited;
         }
+        property.delta_yaw = delta.x;
+        property.delta_pitch = delta.y;
     }
 }
 
 pub fn roll_control<Filter: QueryFilter>(
-    time: Res<Time>,
+    time: OrbitTime,
     config: Res<OrbitControlsConfig>,
     keyboard: Res<ButtonInput<KeyCode>>,
-    mut camera_q: Query<&mut OrbitCamera, Filter>,
+    mut camera_q: Query<(&mut OrbitCamera, Option<&TimeSourceOverride>), Filter>,
 ) {
     if !config.enable || !config.enable_roll {
         return;
     }
     if let Some(button) = config.roll_button {
-        let mut angle = 0.0;
+        let mut direction = 0.0;
         if keyboard.pressed(button.0) {
-            angle += config.roll_speed * time.delta_seconds();
+            direction += 1.0;
         }
         if keyboard.pressed(button.1) {
-            angle -= config.roll_speed * time.delta_seconds();
+            direction -= 1.0;
+        }
+        for (mut property, source_override) in camera_q.iter_mut() {
+            if property.is_added() {
+                continue;
+            }
+            property.roll(direction * config.roll_speed * time.delta_seconds_for(source_override));
+        }
+    }
+}
+
+/// Whether the modifier and button of `OrbitControlsConfig::roll_drag_button` are held.
+fn roll_drag_pressed(
+    config: &OrbitControlsConfig,
+    mouse_input: &ButtonInput<MouseButton>,
+    keyboard: &ButtonInput<KeyCode>,
+) -> bool {
+    config.enable_roll
+        && config.roll_drag_button.is_some_and(|(modifier, button)| {
+            mouse_input.pressed(button)
+                && modifier.is_none_or(|modifier| keyboard.pressed(modifier))
+        })
+}
+
+/// System for rolling the camera by dragging horizontally with `roll_drag_button`.
+pub fn roll_drag_control<Filter: QueryFilter>(
+    config: Res<OrbitControlsConfig>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut camera_q: Query<(&mut OrbitCamera, &Camera), Filter>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut drag_distance: Local<f32>,
+) {
+    if !config.enable || !roll_drag_pressed(&config, &mouse_input, &keyboard) {
+        *drag_distance = 0.0;
+        mouse_motion_events.clear();
+        return;
+    }
+    let mouse_motion = mouse_motion_events
+        .read()
+        .map(|event| event.delta)
+        .sum::<Vec2>();
+    if !exceeds_drag_threshold(&mut drag_distance, mouse_motion, config.drag_threshold) {
+        return;
+    }
+    for (mut property, camera) in camera_q.iter_mut() {
+        if property.is_added() {
+            continue;
+        }
+        if let Some(viewport_size) = camera.physical_viewport_size() {
+            // dragging right turns the scene clockwise, like grabbing it
+            let delta = config.roll_speed * mouse_motion.x / viewport_size.as_vec2().min_element();
+            property.roll(delta);
+        }
+    }
+}
+
+/// System for orbiting the camera with the keyboard.
+///
+/// Every control only accumulates deltas into the `OrbitCamera` and reads input through its own event reader,
+/// so keyboard orbiting composes with mouse panning or rotation within the same frame.
+pub fn keyboard_orbit_control<Filter: QueryFilter>(
+    time: OrbitTime,
+    config: Res<OrbitControlsConfig>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut camera_q: Query<(&mut OrbitCamera, Option<&TimeSourceOverride>), Filter>,
+) {
+    if !config.enable || !config.enable_rotation {
+        return;
+    }
+    if let Some((left, right, up, down)) = config.orbit_keys {
+        let mut direction = Vec2::ZERO;
+        if keyboard.pressed(left) {
+            direction.x += 1.0;
+        }
+        if keyboard.pressed(right) {
+            direction.x -= 1.0;
+        }
+        if keyboard.pressed(up) {
+            direction.y -= 1.0;
+        }
+        if keyboard.pressed(down) {
+            direction.y += 1.0;
+        }
+        for (mut property, source_override) in camera_q.iter_mut() {
+            if property.is_added() {
+                continue;
+            }
+            let delta = config.rotation_speed * time.delta_seconds_for(source_override) * direction;
+            property.orbit(delta.x, delta.y, 0.0);
+        }
+    }
+}
+
+/// System for moving the camera and focus along the view direction with the keyboard.
+pub fn dolly_control<Filter: QueryFilter>(
+    time: OrbitTime,
+    config: Res<OrbitControlsConfig>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut camera_q: Query<(&mut OrbitCamera, &Transform, Option<&TimeSourceOverride>), Filter>,
+) {
+    if !config.enable || !config.enable_pan {
+        return;
+    }
+    if let Some((forward, back)) = config.dolly_keys {
+        let mut direction = 0.0;
+        if keyboard.pressed(forward) {
+            direction += 1.0;
+        }
+        if keyboard.pressed(back) {
+            direction -= 1.0;
+        }
+        if direction == 0.0 {
+            return;
+        }
+        for (mut property, transform, source_override) in camera_q.iter_mut() {
+            if property.is_added() {
+                continue;
+            }
+            let speed = direction * config.dolly_speed * property.radius;
+            let distance = speed * time.delta_seconds_for(source_override);
+            property.dolly_forward(transform, distance);
         }
-        for mut property in camera_q.iter_mut() {
-            property.roll(angle);
+    }
+}
+
+/// System for zooming continuously while one of `zoom_axis_keys` is held, ramping the rate up and down
+/// over `zoom_axis_ramp` like an analog axis.
+pub fn zoom_axis_control<Filter: QueryFilter>(
+    time: OrbitTime,
+    config: Res<OrbitControlsConfig>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut camera_q: Query<
+        (
+            Entity,
+            &mut OrbitCamera,
+            &mut Projection,
+            Option<&TimeSourceOverride>,
+        ),
+        Filter,
+    >,
+    mut rates: Local<HashMap<Entity, f32>>,
+    mut removed_cameras: RemovedComponents<OrbitCamera>,
+) {
+    // a reused id must start from rest instead of the rate of the despawned camera
+    for entity in removed_cameras.read() {
+        rates.remove(&entity);
+    }
+    let Some((zoom_in, zoom_out)) = config.zoom_axis_keys.filter(|_| config.enable_zoom) else {
+        rates.clear();
+        return;
+    };
+    let mut direction = 0.0;
+    if keyboard.pressed(zoom_in) {
+        direction += 1.0;
+    }
+    if keyboard.pressed(zoom_out) {
+        direction -= 1.0;
+    }
+    let target_rate = direction * config.zoom_axis_speed;
+    for (entity, mut property, mut projection, source_override) in camera_q.iter_mut() {
+        if property.is_added() {
+            continue;
+        }
+        let delta_seconds = time.delta_seconds_for(source_override);
+        let rate = rates.entry(entity).or_default();
+        *rate = if config.zoom_axis_ramp > 0.0 {
+            let max_change = config.zoom_axis_speed / config.zoom_axis_ramp * delta_seconds;
+            *rate + (target_rate - *rate).clamp(-max_change, max_change)
+        } else {
+            target_rate
+        };
+        if *rate == 0.0 {
+            continue;
+        }
+        let factor = (-*rate * delta_seconds).exp();
+        apply_zoom(&config, &mut property, &mut projection, factor);
+    }
+}
+
+/// System for panning by whole viewports per key press.
+pub fn page_pan_control<Filter: QueryFilter>(
+    config: Res<OrbitControlsConfig>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut camera_q: Query<(&mut OrbitCamera, &Camera, &Projection), Filter>,
+) {
+    if !config.enable || !config.enable_pan {
+        return;
+    }
+    let Some((left, right, up, down)) = config.page_pan_keys else {
+        return;
+    };
+    let mut screens = Vec2::ZERO;
+    if keyboard.just_pressed(left) {
+        screens.x -= 1.0;
+    }
+    if keyboard.just_pressed(right) {
+        screens.x += 1.0;
+    }
+    if keyboard.just_pressed(up) {
+        screens.y += 1.0;
+    }
+    if keyboard.just_pressed(down) {
+        screens.y -= 1.0;
+    }
+    if screens == Vec2::ZERO {
+        return;
+    }
+    for (mut property, camera, projection) in camera_q.iter_mut() {
+        if property.is_added() {
+            continue;
+        }
+        property.pan_screens(screens, camera, projection);
+    }
+}
+
+/// System for orbiting in discrete steps of `nudge_angle` per key press.
+pub fn nudge_control<Filter: QueryFilter>(
+    mut commands: Commands,
+    config: Res<OrbitControlsConfig>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut camera_q: Query<(Entity, &mut OrbitCamera, &Transform, &Projection), Filter>,
+) {
+    if !config.enable || !config.enable_rotation {
+        return;
+    }
+    let mut direction = Vec2::ZERO;
+    if let Some((left, right)) = config.nudge_yaw_keys {
+        if keyboard.just_pressed(left) {
+            direction.x += 1.0;
+        }
+        if keyboard.just_pressed(right) {
+            direction.x -= 1.0;
+        }
+    }
+    if let Some((up, down)) = config.nudge_pitch_keys {
+        if keyboard.just_pressed(up) {
+            direction.y -= 1.0;
+        }
+        if keyboard.just_pressed(down) {
+            direction.y += 1.0;
+        }
+    }
+    if direction == Vec2::ZERO {
+        return;
+    }
+    let delta = config.nudge_angle * direction;
+    for (entity, mut property, transform, projection) in camera_q.iter_mut() {
+        if config.nudge_transition_duration > 0.0 {
+            let from = property.pose(transform);
+            let to = property
+                .clone()
+                .with_orbit(delta.x, delta.y, 0.0)
+                .pending_pose(transform, projection);
+            commands.entity(entity).try_insert(CameraTransition::new(
+                from,
+                to,
+                config.nudge_transition_duration,
+            ));
+        } else {
+            property.orbit(delta.x, delta.y, 0.0);
+        }
+    }
+}
+
+/// System for snapping the yaw to the nearest of `snap_yaw_on_release` directions at the end of a rotate drag.
+///
+/// Releasing a click that didn't move past `drag_threshold` doesn't snap, so clicks stay clicks.
+pub fn snap_yaw_control<Filter: QueryFilter>(
+    mut commands: Commands,
+    config: Res<OrbitControlsConfig>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    camera_q: Query<(Entity, &OrbitCamera, &Transform, &Projection), Filter>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut drag_distance: Local<f32>,
+) {
+    let motion = mouse_motion_events
+        .read()
+        .map(|event| event.delta.length())
+        .sum::<f32>();
+    if !config.enable || !config.enable_rotation {
+        *drag_distance = 0.0;
+        return;
+    }
+    let (Some(divisions), Some(button)) = (config.snap_yaw_on_release, config.rotate_button) else {
+        *drag_distance = 0.0;
+        return;
+    };
+    if mouse_input.pressed(button) || mouse_input.just_released(button) {
+        *drag_distance += motion;
+    }
+    if !mouse_input.just_released(button) {
+        if !mouse_input.pressed(button) {
+            *drag_distance = 0.0;
+        }
+        return;
+    }
+    let dragged = *drag_distance > 0.0 && *drag_distance >= config.drag_threshold;
+    *drag_distance = 0.0;
+    if divisions == 0 || !dragged {
+        return;
+    }
+    let step = TAU / divisions as f32;
+    for (entity, property, transform, projection) in camera_q.iter() {
+        let from = property.pending_pose(transform, projection);
+        let basis = property.up_axis.unwrap_or_default().basis();
+        let (yaw, pitch, roll) = (basis.inverse() * from.rotation).to_euler(EulerRot::YXZ);
+        let yaw = (yaw / step).round() * step;
+        let to = OrbitCameraPose {
+            rotation: basis * Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll),
+            ..from
+        };
+        commands.entity(entity).try_insert(CameraTransition::new(
+            from,
+            to,
+            config.snap_yaw_duration,
+        ));
+    }
+}
+
+/// System for leveling the camera, keeping the view direction, focus and radius.
+pub fn reset_roll_control<Filter: QueryFilter>(
+    mut commands: Commands,
+    config: Res<OrbitControlsConfig>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut camera_q: Query<
+        (
+            Entity,
+            &mut OrbitCamera,
+            &mut Transform,
+            &Projection,
+            Has<FreezeTransform>,
+        ),
+        Filter,
+    >,
+) {
+    if !config.enable || !config.enable_roll {
+        return;
+    }
+    if !config
+        .reset_roll_button
+        .is_some_and(|button| keyboard.just_pressed(button))
+    {
+        return;
+    }
+    for (entity, mut property, mut transform, projection, frozen) in camera_q.iter_mut() {
+        let from = property.pending_pose(&transform, projection);
+        let forward = from.rotation * Vec3::NEG_Z;
+        let to = OrbitCameraPose {
+            rotation: from_direction_with_up_axis(forward, property.up_axis.unwrap_or_default()),
+            ..from
+        };
+        if config.reset_roll_duration > 0.0 {
+            commands.entity(entity).try_insert(CameraTransition::new(
+                from,
+                to,
+                config.reset_roll_duration,
+            ));
+        } else if !frozen {
+            property.reset_rotation_and_pan_deltas();
+            property.focus = to.focus;
+            transform.rotation = to.rotation;
+        }
+    }
+}
+
+/// System moving the focus to the depth of the supplied screen center hit when a rotate drag starts.
+pub fn auto_focus_control<Filter: QueryFilter>(
+    config: Res<OrbitControlsConfig>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut camera_q: Query<(&mut OrbitCamera, &Transform, &Projection, &ScreenCenterHit), Filter>,
+) {
+    if !config.enable || !config.enable_rotation || !config.auto_focus_screen_center {
+        return;
+    }
+    if !config
+        .rotate_button
+        .is_some_and(|button| mouse_input.just_pressed(button))
+    {
+        return;
+    }
+    for (mut property, transform, projection, hit) in camera_q.iter_mut() {
+        let Some(hit) = hit.0 else {
+            continue;
+        };
+        // move the focus along the view axis to the depth of the hit, so neither the camera nor the view changes,
+        // even if the hit is off center
+        let forward = transform.rotation * Vec3::NEG_Z;
+        let depth = (hit - transform.translation).dot(forward);
+        if depth <= 0.0 {
+            continue;
+        }
+        if let Projection::Perspective(_) = projection {
+            property.radius = depth;
+        }
+        property.focus = transform.translation + forward * depth;
+    }
+}
+
+/// System updating the [`ControlActivity`] of the cameras from the input state of this frame.
+pub fn control_activity<Filter: QueryFilter>(
+    config: Res<OrbitControlsConfig>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut scroll_events: EventReader<MouseWheel>,
+    mut camera_q: Query<&mut ControlActivity, Filter>,
+) {
+    let scrolled = scroll_events.read().count() > 0;
+    let any_pressed = |keys: &[KeyCode]| keys.iter().any(|key| keyboard.pressed(*key));
+    let roll_dragging = roll_drag_pressed(&config, &mouse_input, &keyboard);
+    let rotating = config.enable_rotation
+        && (config
+            .rotate_button
+            .is_some_and(|button| mouse_input.pressed(button) && !roll_dragging)
+            || config
+                .orbit_keys
+                .is_some_and(|(left, right, up, down)| any_pressed(&[left, right, up, down])));
+    let panning = config.enable_pan
+        && (config
+            .pan_button
+            .is_some_and(|button| mouse_input.pressed(button) && !roll_dragging)
+            || config
+                .dolly_keys
+                .is_some_and(|(forward, back)| any_pressed(&[forward, back])));
+    let zooming = config.enable_zoom
+        && (scrolled
+            && config
+                .zoom_button
+                .is_none_or(|button| keyboard.pressed(button))
+            || config
+                .zoom_axis_keys
+                .is_some_and(|(zoom_in, zoom_out)| any_pressed(&[zoom_in, zoom_out])));
+    let rolling = config.enable_roll
+        && config
+            .roll_button
+            .is_some_and(|(left, right)| any_pressed(&[left, right]))
+        || roll_dragging;
+    let activity = ControlActivity {
+        rotating,
+        panning,
+        zooming,
+        rolling,
+    };
+    for mut current in camera_q.iter_mut() {
+        current.set_if_neq(activity);
+    }
+}
+
+/// Event sent when the rotate or pan button is released without dragging further than
+/// `OrbitControlsConfig::drag_threshold`, e.g. to open a context menu on right-click.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct CameraClick {
+    /// The camera whose viewport was clicked.
+    pub camera: Entity,
+    pub button: MouseButton,
+    /// The clicked point on the plane through the focus facing the camera, raycast the scene for the exact hit.
+    /// `None` if the point couldn't be determined.
+    pub world_point: Option<Vec3>,
+}
+
+/// System sending [`CameraClick`] events for clicks of the control buttons that didn't turn into a drag.
+pub fn camera_click<Filter: QueryFilter>(
+    config: Res<OrbitControlsConfig>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<(Entity, &OrbitCamera, &Transform, &Camera, &Projection), Filter>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut click_events: EventWriter<CameraClick>,
+    mut drag_distances: Local<HashMap<MouseButton, f32>>,
+) {
+    let mouse_motion = mouse_motion_events
+        .read()
+        .map(|event| event.delta)
+        .sum::<Vec2>();
+    let cursor_pos = window_q
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+    for button in [config.rotate_button, config.pan_button]
+        .into_iter()
+        .flatten()
+    {
+        if mouse_input.just_pressed(button) {
+            drag_distances.insert(button, 0.0);
+        } else if mouse_input.pressed(button) {
+            if let Some(distance) = drag_distances.get_mut(&button) {
+                exceeds_drag_threshold(distance, mouse_motion, config.drag_threshold);
+            }
+        }
+        if !mouse_input.just_released(button) {
+            continue;
+        }
+        let Some(mut distance) = drag_distances.remove(&button) else {
+            continue;
+        };
+        let Some(cursor_pos) = cursor_pos else {
+            continue;
+        };
+        // like the drag controls, but a release without any motion is a click even without a threshold
+        if distance > 0.0
+            && exceeds_drag_threshold(&mut distance, Vec2::ZERO, config.drag_threshold)
+        {
+            continue;
+        }
+        for (entity, property, transform, camera, projection) in camera_q.iter() {
+            let Some(viewport) = camera.logical_viewport_rect() else {
+                continue;
+            };
+            if !viewport.contains(cursor_pos) {
+                continue;
+            }
+            let back: Vec3 = transform.back().into();
+            let world_point = cursor_ray(
+                property,
+                transform,
+                camera,
+                projection,
+                cursor_pos - viewport.min,
+            )
+            .and_then(|ray| {
+                ray.intersect_plane(property.focus, Plane3d::new(back))
+                    .map(|distance| ray.get_point(distance))
+            });
+            click_events.send(CameraClick {
+                camera: entity,
+                button,
+                world_point,
+            });
+        }
+    }
+}
+
+/// System keeping the vertical extent of the view when the viewport is resized,
+/// see `OrbitControlsConfig::maintain_framing_on_resize`.
+pub fn maintain_framing<Filter: QueryFilter>(
+    config: Res<OrbitControlsConfig>,
+    mut camera_q: Query<(Entity, &mut OrbitCamera, &Camera, &Projection), Filter>,
+    mut viewport_sizes: Local<HashMap<Entity, Vec2>>,
+    mut removed_cameras: RemovedComponents<OrbitCamera>,
+) {
+    for entity in removed_cameras.read() {
+        viewport_sizes.remove(&entity);
+    }
+    for (entity, mut property, camera, projection) in camera_q.iter_mut() {
+        let Some(viewport_size) = camera.logical_viewport_size() else {
+            continue;
+        };
+        let Some(previous_size) = viewport_sizes.insert(entity, viewport_size) else {
+            continue;
+        };
+        if !config.maintain_framing_on_resize || previous_size == viewport_size {
+            continue;
+        }
+        if let Projection::Orthographic(p) = projection {
+            property.radius =
+                framing_radius_after_resize(p, property.radius, previous_size, viewport_size);
+        }
+    }
+}
+
+/// Returns the radius keeping the vertical extent of an orthographic view when its logical viewport size changes
+/// from `previous_size` to `viewport_size`.
+pub fn framing_radius_after_resize(
+    projection: &OrthographicProjection,
+    radius: f32,
+    previous_size: Vec2,
+    viewport_size: Vec2,
+) -> f32 {
+    let previous_height = calculate_orthographic_area(projection, 1.0, previous_size).y;
+    let height = calculate_orthographic_area(projection, 1.0, viewport_size).y;
+    if height > 0.0 {
+        radius * previous_height / height
+    } else {
+        radius
+    }
+}
+
+/// Event sent once when a camera comes to rest after moving, i.e. input stopped and all smoothing has converged.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct CameraSettled(pub Entity);
+
+/// The remaining motion below which the smoothing counts as converged.
+const SETTLED_EPSILON: f32 = 1e-4;
+
+type SettledCameraQuery<'w, 's, Filter> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static OrbitCamera,
+        Option<&'static TargetZoom>,
+        Option<&'static TargetPan>,
+        Option<&'static TargetRotation>,
+        Has<CameraTransition>,
+    ),
+    Filter,
+>;
+
+/// System sending [`CameraSettled`] when a camera stops moving.
+pub fn camera_settled<Filter: QueryFilter>(
+    camera_q: SettledCameraQuery<Filter>,
+    mut settled_events: EventWriter<CameraSettled>,
+    mut moving: Local<HashSet<Entity>>,
+    mut previous_states: Local<HashMap<Entity, (Vec3, f32)>>,
+    mut removed_cameras: RemovedComponents<OrbitCamera>,
+) {
+    // a reused id must not compare against the state of the despawned camera
+    for entity in removed_cameras.read() {
+        moving.remove(&entity);
+        previous_states.remove(&entity);
+    }
+    for (entity, property, target_zoom, target_pan, target_rotation, in_transition) in
+        camera_q.iter()
+    {
+        let state = (property.focus, property.radius);
+        let changed = previous_states.insert(entity, state).is_some_and(
+            |(previous_focus, previous_radius)| {
+                // relative to the radius, since the smoothing approaches its target exponentially
+                let epsilon = SETTLED_EPSILON * property.radius.abs().max(1.0);
+                previous_focus.distance(property.focus) > epsilon
+                    || (previous_radius - property.radius).abs() > epsilon
+            },
+        );
+        let is_moving = changed
+            || in_transition
+            || Vec3::new(
+                property.delta_yaw,
+                property.delta_pitch,
+                property.delta_roll,
+            )
+            .length()
+                > SETTLED_EPSILON
+            || property.pan.length() > SETTLED_EPSILON
+            || property.state.target_radius.is_some()
+            || target_zoom.is_some_and(|target_zoom| {
+                (target_zoom.remaining - 1.0).abs() > SETTLED_EPSILON
+                    || target_zoom.spring_velocity.abs() > SETTLED_EPSILON
+            })
+            || target_pan.is_some_and(|target_pan| {
+                target_pan.remaining.length() > SETTLED_EPSILON
+                    || target_pan.velocity.length() > SETTLED_EPSILON
+                    || target_pan.spring_velocity.length() > SETTLED_EPSILON
+            })
+            || target_rotation
+                .is_some_and(|target_rotation| target_rotation.0.length() > SETTLED_EPSILON);
+        if is_moving {
+            moving.insert(entity);
+        } else if moving.remove(&entity) {
+            settled_events.send(CameraSettled(entity));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::camera::{CameraProjection, ScalingMode, Viewport};
+
+    use crate::{
+        response::ResponseCurve,
+        test_util::{controls_app, next_frame, spawn_camera, FRAME_TIME, VIEWPORT_SIZE},
+        OrbitAngles,
+    };
+
+    #[test]
+    fn rotation_starts_past_the_drag_threshold() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .drag_threshold = 10.0;
+        let camera = spawn_camera(&mut app, OrbitCamera::default());
+        next_frame(&mut app);
+        let rotation = |app: &App| app.world.get::<Transform>(camera).unwrap().rotation;
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(4.0, 0.0),
+        });
+        next_frame(&mut app);
+        assert_eq!(rotation(&app), Quat::IDENTITY);
+
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(8.0, 0.0),
+        });
+        next_frame(&mut app);
+        let dragged = rotation(&app);
+        assert!(!dragged.abs_diff_eq(Quat::IDENTITY, 1e-6));
+
+        // releasing resets the dragged distance, so the next click stays below the threshold again
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Left);
+        next_frame(&mut app);
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(4.0, 0.0),
+        });
+        next_frame(&mut app);
+        assert!(rotation(&app).abs_diff_eq(dragged, 1e-6));
+    }
+
+    #[test]
+    fn a_scroll_burst_zooms_further_with_acceleration() {
+        let burst_radius = |zoom_acceleration: f32| {
+            let mut app = controls_app();
+            let mut config = app.world.resource_mut::<OrbitControlsConfig>();
+            config.zoom_smoothness = 0.0;
+            config.zoom_acceleration = zoom_acceleration;
+            let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 10.0));
+            next_frame(&mut app);
+            let window = Entity::PLACEHOLDER;
+            for _ in 0..5 {
+                app.world.send_event(MouseWheel {
+                    unit: MouseScrollUnit::Line,
+                    x: 0.0,
+                    y: 1.0,
+                    window,
+                });
+            }
+            next_frame(&mut app);
+            app.world.get::<OrbitCamera>(camera).unwrap().radius
+        };
+
+        let zoom_speed = OrbitControlsConfig::default().zoom_speed;
+        // without acceleration, each step scales the radius like before it was added
+        let linear = burst_radius(0.0);
+        assert!((linear - 10.0 * (1.0 - zoom_speed).powi(5)).abs() < 1e-3);
+        assert!(burst_radius(0.5) < linear);
+        assert!(burst_radius(1000.0) > 0.0);
+    }
+
+    #[test]
+    fn zooming_fully_in_keeps_the_orthographic_scale_positive() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .zoom_smoothness = 0.0;
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 1.0));
+        app.world
+            .entity_mut(camera)
+            .insert(Projection::Orthographic(OrthographicProjection::default()));
+        next_frame(&mut app);
+        for _ in 0..100 {
+            app.world.send_event(MouseWheel {
+                unit: MouseScrollUnit::Line,
+                x: 0.0,
+                y: 10.0,
+                window: Entity::PLACEHOLDER,
+            });
+            next_frame(&mut app);
+        }
+
+        let mut projection = app.world.get::<Projection>(camera).unwrap().clone();
+        let Projection::Orthographic(p) = &projection else {
+            panic!("the projection should stay orthographic");
+        };
+        assert!(p.scale > 0.0);
+        let viewport = VIEWPORT_SIZE.as_vec2();
+        projection.update(viewport.x, viewport.y);
+        let matrix = projection.get_projection_matrix();
+        assert!(matrix.is_finite());
+        assert!(matrix.determinant() != 0.0);
+    }
+
+    #[test]
+    fn control_systems_are_skipped_while_disabled() {
+        #[derive(Resource, Default)]
+        struct Runs(u32);
+
+        let mut app = controls_app();
+        app.init_resource::<Runs>().add_systems(
+            Update,
+            (|mut runs: ResMut<Runs>| runs.0 += 1).in_set(OrbitControlsSystemSet),
+        );
+        app.world.resource_mut::<OrbitControlsConfig>().enable = false;
+        next_frame(&mut app);
+        assert_eq!(app.world.resource::<Runs>().0, 0);
+
+        app.world.resource_mut::<OrbitControlsConfig>().enable = true;
+        next_frame(&mut app);
+        assert_eq!(app.world.resource::<Runs>().0, 1);
+    }
+
+    #[test]
+    fn keyboard_orbit_and_mouse_pan_apply_in_the_same_frame() {
+        let mut app = controls_app();
+        app.world.resource_mut::<OrbitControlsConfig>().orbit_keys = Some((
+            KeyCode::ArrowLeft,
+            KeyCode::ArrowRight,
+            KeyCode::ArrowUp,
+            KeyCode::ArrowDown,
+        ));
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        next_frame(&mut app);
+        let before = *app.world.get::<Transform>(camera).unwrap();
+
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ArrowLeft);
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(10.0, 0.0),
+        });
+        next_frame(&mut app);
+
+        let orbit = app.world.get::<OrbitCamera>(camera).unwrap();
+        let transform = app.world.get::<Transform>(camera).unwrap();
+        assert!(orbit.focus.length() > 1e-4);
+        assert!(!transform.rotation.abs_diff_eq(before.rotation, 1e-6));
+        // the camera still orbits the panned focus
+        let offset = transform.translation - orbit.focus;
+        assert!((offset.length() - 5.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn invalid_config_edits_are_corrected() {
+        let mut app = controls_app();
+        next_frame(&mut app);
+        let mut config = app.world.resource_mut::<OrbitControlsConfig>();
+        config.zoom_speed = -1.0;
+        config.pan_smoothness = 2.0;
+        config.fov_limit = 2.0..=0.5;
+        config.snap_increment = Some(f32::NAN);
+        config.radius_snap = Some(vec![4.0, -1.0, f32::INFINITY]);
+        next_frame(&mut app);
+
+        let config = app.world.resource::<OrbitControlsConfig>();
+        assert_eq!(config.zoom_speed, 0.0);
+        assert_eq!(config.pan_smoothness, 1.0);
+        assert_eq!(config.fov_limit, 0.5..=2.0);
+        assert_eq!(config.snap_increment, Some(0.0));
+        assert_eq!(config.radius_snap, Some(vec![4.0]));
+        // the rest is left alone
+        assert_eq!(
+            config.rotation_speed,
+            OrbitControlsConfig::default().rotation_speed
+        );
+    }
+
+    #[test]
+    fn fine_zoom_modifier_zooms_in_smaller_steps() {
+        let zoomed_radius = |hold_modifier: bool| {
+            let mut app = controls_app();
+            let mut config = app.world.resource_mut::<OrbitControlsConfig>();
+            config.zoom_smoothness = 0.0;
+            config.fine_zoom_modifier = Some(KeyCode::ShiftLeft);
+            let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 10.0));
+            next_frame(&mut app);
+            if hold_modifier {
+                app.world
+                    .resource_mut::<ButtonInput<KeyCode>>()
+                    .press(KeyCode::ShiftLeft);
+            }
+            app.world.send_event(MouseWheel {
+                unit: MouseScrollUnit::Line,
+                x: 0.0,
+                y: 1.0,
+                window: Entity::PLACEHOLDER,
+            });
+            next_frame(&mut app);
+            app.world.get::<OrbitCamera>(camera).unwrap().radius
+        };
+
+        let coarse = 10.0 - zoomed_radius(false);
+        let fine = 10.0 - zoomed_radius(true);
+        assert!(fine > 0.0);
+        assert!(fine < 0.2 * coarse);
+    }
+
+    #[test]
+    fn rotation_snaps_to_the_increment_while_the_modifier_is_held() {
+        let increment = PI / 8.0;
+        let mut app = controls_app();
+        let mut config = app.world.resource_mut::<OrbitControlsConfig>();
+        config.snap_increment = Some(increment);
+        config.snap_modifier = Some(KeyCode::ShiftLeft);
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world.entity_mut(camera).insert(OrbitAngles::default());
+        next_frame(&mut app);
+
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ShiftLeft);
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        // 30 pixels of the 600 pixel high viewport orbit by PI / 20, less than an increment
+        for _ in 0..9 {
+            app.world.send_event(MouseMotion {
+                delta: Vec2::new(-30.0, 0.0),
+            });
+            next_frame(&mut app);
+            let yaw = app.world.get::<OrbitAngles>(camera).unwrap().yaw;
+            let steps = yaw / increment;
+            assert!((steps - steps.round()).abs() < 1e-4);
+        }
+        // 9 * PI / 20 is 3.6 increments
+        let yaw = app.world.get::<OrbitAngles>(camera).unwrap().yaw;
+        assert!((yaw - 3.0 * increment).abs() < 1e-4);
+    }
+
+    #[test]
+    fn perspective_and_orthographic_pan_alike_at_the_same_apparent_size() {
+        let mut app = controls_app();
+        let viewport = VIEWPORT_SIZE.as_vec2();
+        let perspective = PerspectiveProjection {
+            aspect_ratio: viewport.x / viewport.y,
+            ..default()
+        };
+        // an orthographic scale showing the same height as the perspective focus plane
+        let radius = 10.0;
+        let scale = 2.0 * radius * (0.5 * perspective.fov).tan();
+        let perspective_camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, radius));
+        app.world
+            .entity_mut(perspective_camera)
+            .insert(Projection::Perspective(perspective));
+        let orthographic_camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, scale));
+        app.world
+            .entity_mut(orthographic_camera)
+            .insert(Projection::Orthographic(OrthographicProjection {
+                scaling_mode: ScalingMode::FixedVertical(1.0),
+                ..default()
+            }));
+        next_frame(&mut app);
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(30.0, -20.0),
+        });
+        next_frame(&mut app);
+
+        let focus = |camera: Entity| app.world.get::<OrbitCamera>(camera).unwrap().focus;
+        let perspective_pan = focus(perspective_camera);
+        let orthographic_pan = focus(orthographic_camera);
+        assert!(perspective_pan.length() > 0.1);
+        assert!(perspective_pan.abs_diff_eq(orthographic_pan, 1e-4));
+    }
+
+    #[test]
+    fn nudge_orbits_by_the_nudge_angle_per_press() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .nudge_yaw_keys = Some((KeyCode::ArrowLeft, KeyCode::ArrowRight));
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world.entity_mut(camera).insert(OrbitAngles::default());
+        next_frame(&mut app);
+
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ArrowLeft);
+        next_frame(&mut app);
+        let nudge_angle = app.world.resource::<OrbitControlsConfig>().nudge_angle;
+        let yaw = app.world.get::<OrbitAngles>(camera).unwrap().yaw;
+        assert!((yaw - nudge_angle).abs() < 1e-5);
+
+        // holding the key doesn't repeat the nudge
+        next_frame(&mut app);
+        let held = app.world.get::<OrbitAngles>(camera).unwrap().yaw;
+        assert!((held - nudge_angle).abs() < 1e-5);
+    }
+
+    #[test]
+    fn nudge_is_smoothed_on_the_frame_it_is_pressed() {
+        let mut app = controls_app();
+        let mut config = app.world.resource_mut::<OrbitControlsConfig>();
+        config.nudge_yaw_keys = Some((KeyCode::ArrowLeft, KeyCode::ArrowRight));
+        config.yaw_smoothness = 0.5;
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world.entity_mut(camera).insert(OrbitAngles::default());
+        next_frame(&mut app);
+
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ArrowLeft);
+        next_frame(&mut app);
+        let nudge_angle = app.world.resource::<OrbitControlsConfig>().nudge_angle;
+        let yaw = app.world.get::<OrbitAngles>(camera).unwrap().yaw;
+        assert!((yaw - 0.5 * nudge_angle).abs() < 1e-5);
+    }
+
+    #[test]
+    fn smoothed_radius_eases_whichever_order_it_is_set_in() {
+        #[derive(Resource, Default)]
+        struct SetRadius(Option<f32>);
+
+        // set before the controls from outside the schedule, and after them from a system
+        for set_from_system in [false, true] {
+            let mut app = controls_app();
+            app.init_resource::<SetRadius>().add_systems(
+                Update,
+                (|mut set: ResMut<SetRadius>, mut camera_q: Query<&mut OrbitCamera>| {
+                    if let Some(radius) = set.0.take() {
+                        camera_q.single_mut().set_radius_smoothed(radius);
+                    }
+                })
+                .after(OrbitControlsSystemSet),
+            );
+            let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 4.0));
+            next_frame(&mut app);
+            if set_from_system {
+                app.world.resource_mut::<SetRadius>().0 = Some(8.0);
+            } else {
+                app.world
+                    .get_mut::<OrbitCamera>(camera)
+                    .unwrap()
+                    .set_radius_smoothed(8.0);
+            }
+
+            let mut radii = Vec::new();
+            for _ in 0..60 {
+                next_frame(&mut app);
+                radii.push(app.world.get::<OrbitCamera>(camera).unwrap().radius);
+            }
+            let first_step = radii.iter().copied().find(|radius| *radius != 4.0).unwrap();
+            assert!(first_step < 7.0, "snapped to {first_step}");
+            assert!(radii.windows(2).all(|pair| pair[0] <= pair[1] + 1e-5));
+            assert!((radii.last().unwrap() - 8.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn pan_axis_lock_keeps_the_dominant_axis() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .pan_axis_lock_modifier = Some(KeyCode::ShiftLeft);
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        next_frame(&mut app);
+
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ShiftLeft);
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        // diagonal motion, mostly horizontal at first
+        for delta in [Vec2::new(10.0, 6.0), Vec2::new(4.0, 12.0)] {
+            app.world.send_event(MouseMotion { delta });
+            next_frame(&mut app);
+        }
+
+        // the camera looks down -Z, so screen axes are world X and Y
+        let focus = app.world.get::<OrbitCamera>(camera).unwrap().focus;
+        assert!(focus.x.abs() > 1e-3);
+        assert!(focus.y.abs() < 1e-6);
+    }
+
+    #[test]
+    fn releasing_a_drag_animates_the_yaw_to_the_nearest_division() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .snap_yaw_on_release = Some(8);
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world.entity_mut(camera).insert(OrbitAngles::default());
+        next_frame(&mut app);
+        let yaw = |app: &App| app.world.get::<OrbitAngles>(camera).unwrap().yaw;
+
+        // 96 pixels of the 600 pixel high viewport orbit by about 0.5, past half of the PI / 4 division
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(-96.0, 0.0),
+        });
+        next_frame(&mut app);
+        let released_at = yaw(&app);
+        assert!((released_at - 0.5).abs() < 0.01);
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Left);
+        next_frame(&mut app);
+        next_frame(&mut app);
+        let animating = yaw(&app);
+        assert!(animating > released_at && animating < PI / 4.0 - 1e-3);
+
+        for _ in 0..30 {
+            next_frame(&mut app);
+        }
+        assert!((yaw(&app) - PI / 4.0).abs() < 1e-4);
+        assert!(app.world.get::<OrbitAngles>(camera).unwrap().pitch.abs() < 1e-4);
+    }
+
+    #[test]
+    fn reset_roll_levels_the_camera_keeping_the_view_direction() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .reset_roll_button = Some(KeyCode::KeyR);
+        let camera = spawn_camera(
+            &mut app,
+            OrbitCamera::new(Vec3::new(1.0, 2.0, 0.0), 5.0).with_orbit(0.5, 0.3, 0.0),
+        );
+        app.world.entity_mut(camera).insert(OrbitAngles::default());
+        next_frame(&mut app);
+        app.world.get_mut::<OrbitCamera>(camera).unwrap().roll(0.4);
+        next_frame(&mut app);
+        let rolled = *app.world.get::<Transform>(camera).unwrap();
+        assert!(app.world.get::<OrbitAngles>(camera).unwrap().roll.abs() > 0.2);
+
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyR);
+        for _ in 0..30 {
+            next_frame(&mut app);
+        }
+        let transform = app.world.get::<Transform>(camera).unwrap();
+        let orbit = app.world.get::<OrbitCamera>(camera).unwrap();
+        assert!(app.world.get::<OrbitAngles>(camera).unwrap().roll.abs() < 1e-4);
+        assert!(Vec3::from(transform.forward()).abs_diff_eq(rolled.forward().into(), 1e-4));
+        assert!(transform.translation.abs_diff_eq(rolled.translation, 1e-4));
+        assert_eq!(orbit.radius, 5.0);
+    }
+
+    #[test]
+    fn per_axis_normalization_yaws_by_the_rotation_speed_for_a_full_width_drag() {
+        let mut app = controls_app();
+        let mut config = app.world.resource_mut::<OrbitControlsConfig>();
+        config.per_axis_viewport_normalization = true;
+        config.rotation_speed = 1.0;
+        let camera = app
+            .world
+            .spawn((
+                OrbitCamera::new(Vec3::ZERO, 5.0),
+                Transform::default(),
+                Projection::default(),
+                Camera {
+                    viewport: Some(Viewport {
+                        physical_size: UVec2::new(1600, 400),
+                        ..default()
+                    }),
+                    ..default()
+                },
+                OrbitAngles::default(),
+            ))
+            .id();
+        next_frame(&mut app);
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(-1600.0, 0.0),
+        });
+        next_frame(&mut app);
+        let yaw = app.world.get::<OrbitAngles>(camera).unwrap().yaw;
+        assert!((yaw - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn full_viewport_drag_orbits_by_the_rotation_speed() {
+        let viewport_size = Vec2::new(800.0, 600.0);
+        // across the smaller dimension, dragging left yaws positively and dragging down pitches positively
+        let orbit = pixel_delta_to_orbit(Vec2::new(-600.0, 600.0), viewport_size, PI);
+        assert!(orbit.abs_diff_eq(Vec2::new(PI, PI), 1e-5));
+        let half = pixel_delta_to_orbit(Vec2::new(300.0, 0.0), viewport_size, 2.0);
+        assert!(half.abs_diff_eq(Vec2::new(-1.0, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn mouse_motion_of_a_frame_is_coalesced_before_the_response_curve() {
+        let mut app = controls_app();
+        // a nonlinear curve gives a different result when applied per event
+        app.world.resource_mut::<ResponseCurves>().rotation = ResponseCurve::Power(1.5);
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world.entity_mut(camera).insert(OrbitAngles::default());
+        next_frame(&mut app);
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        for _ in 0..3 {
+            app.world.send_event(MouseMotion {
+                delta: Vec2::new(-20.0, 0.0),
+            });
+        }
+        next_frame(&mut app);
+        let rotation_speed = app.world.resource::<OrbitControlsConfig>().rotation_speed;
+        let expected = pixel_delta_to_orbit(
+            Vec2::new(-60f32.powf(1.5), 0.0),
+            VIEWPORT_SIZE.as_vec2(),
+            rotation_speed,
+        );
+        let yaw = app.world.get::<OrbitAngles>(camera).unwrap().yaw;
+        assert!((yaw - expected.x).abs() < 1e-4);
+    }
+
+    #[test]
+    fn trackpad_pixels_zoom_like_an_equivalent_wheel_step() {
+        let zoomed_radius = |unit: MouseScrollUnit, y: f32, count: usize| {
+            let mut app = controls_app();
+            app.world
+                .resource_mut::<OrbitControlsConfig>()
+                .zoom_smoothness = 0.0;
+            let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 10.0));
+            next_frame(&mut app);
+            for _ in 0..count {
+                app.world.send_event(MouseWheel {
+                    unit,
+                    x: 0.0,
+                    y,
+                    window: Entity::PLACEHOLDER,
+                });
+            }
+            next_frame(&mut app);
+            app.world.get::<OrbitCamera>(camera).unwrap().radius
+        };
+
+        // the default speeds make a line step equal to 20 pixels
+        let config = OrbitControlsConfig::default();
+        let pixels_per_line = config.zoom_speed / config.trackpad_zoom_speed;
+        assert!((pixels_per_line - 20.0).abs() < 1e-4);
+        let wheel = zoomed_radius(MouseScrollUnit::Line, 1.0, 1);
+        assert!((wheel - 10.0 * (1.0 - config.zoom_speed)).abs() < 1e-4);
+        // the pixels of a frame are applied exponentially, which matches the line step to first order
+        let trackpad = zoomed_radius(MouseScrollUnit::Pixel, 0.5, 40);
+        assert!((trackpad - 10.0 * (-config.zoom_speed).exp()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn rotation_pivots_around_the_screen_center_hit() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .auto_focus_screen_center = true;
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 10.0));
+        // slightly off center, 6 units in front of the camera
+        app.world
+            .entity_mut(camera)
+            .insert(ScreenCenterHit(Some(Vec3::new(0.3, -0.2, 4.0))));
+        next_frame(&mut app);
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(-60.0, 0.0),
+        });
+        next_frame(&mut app);
+
+        let orbit = app.world.get::<OrbitCamera>(camera).unwrap();
+        let transform = app.world.get::<Transform>(camera).unwrap();
+        assert!(orbit.focus.abs_diff_eq(Vec3::new(0.0, 0.0, 4.0), 1e-5));
+        assert!((orbit.radius - 6.0).abs() < 1e-5);
+        assert!((transform.translation.distance(orbit.focus) - 6.0).abs() < 1e-4);
+        assert!(transform.translation.x.abs() > 0.1);
+    }
+
+    #[test]
+    fn setting_the_focus_mid_pan_drops_the_remaining_pan() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .pan_smoothness = 0.8;
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        next_frame(&mut app);
+        let focus = |app: &App| app.world.get::<OrbitCamera>(camera).unwrap().focus;
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(100.0, 0.0),
+        });
+        next_frame(&mut app);
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Right);
+        let dragged = focus(&app);
+        next_frame(&mut app);
+        // still easing in the rest of the pan
+        assert!(focus(&app).distance(dragged) > 1e-4);
+
+        let target = Vec3::new(5.0, 1.0, 0.0);
+        app.world.get_mut::<OrbitCamera>(camera).unwrap().focus = target;
+        for _ in 0..10 {
+            next_frame(&mut app);
+            assert!(focus(&app).abs_diff_eq(target, 1e-6));
+        }
+    }
+
+    #[test]
+    fn control_activity_reports_rotating_while_the_button_is_held() {
+        let mut app = controls_app();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        next_frame(&mut app);
+        let activity = |app: &App| *app.world.get::<ControlActivity>(camera).unwrap();
+        assert!(!activity(&app).any());
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        next_frame(&mut app);
+        assert_eq!(
+            activity(&app),
+            ControlActivity {
+                rotating: true,
+                ..default()
+            }
+        );
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Left);
+        next_frame(&mut app);
+        assert!(!activity(&app).rotating);
+    }
+
+    #[test]
+    fn smoothing_never_moves_faster_than_the_caps() {
+        let mut app = controls_app();
+        let mut config = app.world.resource_mut::<OrbitControlsConfig>();
+        config.max_zoom_speed = Some(1.0);
+        config.max_pan_speed = Some(2.0);
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 1.0));
+        next_frame(&mut app);
+
+        let mut orbit = app.world.get_mut::<OrbitCamera>(camera).unwrap();
+        orbit.set_radius_smoothed(1000.0);
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(-10_000.0, 0.0),
+        });
+        let (mut radius, mut focus) = (1.0f32, Vec3::ZERO);
+        for _ in 0..30 {
+            next_frame(&mut app);
+            let orbit = app.world.get::<OrbitCamera>(camera).unwrap();
+            assert!((orbit.radius / radius).ln() <= FRAME_TIME + 1e-5);
+            assert!(orbit.focus.distance(focus) <= 2.0 * FRAME_TIME + 1e-5);
+            (radius, focus) = (orbit.radius, orbit.focus);
+        }
+        assert!(radius > 1.0 && focus.length() > 0.0);
+    }
+
+    #[test]
+    fn set_controls_config_clamps_invalid_values_on_apply() {
+        let mut app = controls_app();
+        next_frame(&mut app);
+        app.world.resource_mut::<OrbitControlsConfig>().enable = false;
+        app.world.send_event(SetControlsConfig(OrbitControlsConfig {
+            rotation_speed: -2.0,
+            pan_speed: 3.0,
+            ..default()
+        }));
+        next_frame(&mut app);
+
+        let config = app.world.resource::<OrbitControlsConfig>();
+        assert_eq!(config.rotation_speed, 0.0);
+        assert_eq!(config.pan_speed, 3.0);
+        // applied while the controls were disabled
+        assert!(config.enable);
+    }
+
+    #[test]
+    fn nearest_radius_stop_compares_by_ratio() {
+        let stops = [1.0, 4.0, 8.0];
+        assert_eq!(nearest_radius_stop(&stops, 5.5), Some(4.0));
+        assert_eq!(nearest_radius_stop(&stops, 5.8), Some(8.0));
+        // 2.2 is closer to 1 in absolute terms, but closer to 4 by ratio
+        assert_eq!(nearest_radius_stop(&stops, 2.2), Some(4.0));
+        assert_eq!(nearest_radius_stop(&[0.0, -1.0], 2.0), None);
+    }
+
+    #[test]
+    fn zoom_settles_on_the_nearer_radius_stop() {
+        let settled_radius = |lines: usize| {
+            let mut app = controls_app();
+            app.world.resource_mut::<OrbitControlsConfig>().radius_snap = Some(vec![4.0, 8.0]);
+            let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 8.0));
+            next_frame(&mut app);
+            for _ in 0..lines {
+                app.world.send_event(MouseWheel {
+                    unit: MouseScrollUnit::Line,
+                    x: 0.0,
+                    y: 1.0,
+                    window: Entity::PLACEHOLDER,
+                });
+            }
+            for _ in 0..120 {
+                next_frame(&mut app);
+            }
+            app.world.get::<OrbitCamera>(camera).unwrap().radius
+        };
+
+        // one line zooms to about 6.5, three lines to about 4.4
+        assert!((settled_radius(1) - 8.0).abs() < 1e-3);
+        assert!((settled_radius(3) - 4.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn equivalent_zoom_radius_keeps_the_focus_plane_size() {
+        let radius = equivalent_zoom_radius(10.0, 1.0, 0.5);
+        // the height of the view on the focus plane is the same
+        assert!((radius * 0.25f32.tan() - 10.0 * 0.5f32.tan()).abs() < 1e-4);
+        assert_eq!(equivalent_zoom_radius(10.0, 0.7, 0.7), 10.0);
+    }
+
+    #[test]
+    fn switching_zoom_modes_keeps_the_apparent_size_of_the_focus() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .zoom_smoothness = 0.0;
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 10.0));
+        next_frame(&mut app);
+        // the size of an object at the focus relative to the view height
+        let apparent_size = |app: &App| {
+            let radius = app.world.get::<OrbitCamera>(camera).unwrap().radius;
+            let Projection::Perspective(p) = app.world.get::<Projection>(camera).unwrap() else {
+                unreachable!();
+            };
+            (p.fov, 1.0 / (radius * (0.5 * p.fov).tan()))
+        };
+        let (dolly_fov, _) = apparent_size(&app);
+
+        app.world.resource_mut::<OrbitControlsConfig>().zoom_mode = ZoomMode::Fov;
+        next_frame(&mut app);
+        app.world.send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: 3.0,
+            window: Entity::PLACEHOLDER,
+        });
+        next_frame(&mut app);
+        let (zoomed_fov, before) = apparent_size(&app);
+        assert!(zoomed_fov < dolly_fov);
+
+        app.world.resource_mut::<OrbitControlsConfig>().zoom_mode = ZoomMode::Dolly;
+        next_frame(&mut app);
+        let (restored_fov, after) = apparent_size(&app);
+        assert_eq!(restored_fov, dolly_fov);
+        assert!((after / before - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn zoom_below_the_threshold_skips_the_smoothing() {
+        let radius_after_one_frame = |lines: f32| {
+            let mut app = controls_app();
+            app.world
+                .resource_mut::<OrbitControlsConfig>()
+                .smooth_above_threshold = Some(SmoothingThreshold {
+                rotation: 0.1,
+                pan: 0.1,
+                zoom: 0.1,
+            });
+            let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 10.0));
+            next_frame(&mut app);
+            app.world.send_event(MouseWheel {
+                unit: MouseScrollUnit::Line,
+                x: 0.0,
+                y: lines,
+                window: Entity::PLACEHOLDER,
+            });
+            next_frame(&mut app);
+            app.world.get::<OrbitCamera>(camera).unwrap().radius
+        };
+
+        let zoom_speed = OrbitControlsConfig::default().zoom_speed;
+        // a zoom of about 0.06 in natural-log units is applied at once
+        let small = radius_after_one_frame(0.3);
+        assert!((small - 10.0 * (1.0 - 0.3 * zoom_speed)).abs() < 1e-4);
+        // one of about 0.9 is eased in over the next frames
+        let large = radius_after_one_frame(3.0);
+        assert!(large < 10.0);
+        assert!(large > 10.0 * (1.0 - 3.0 * zoom_speed) + 0.1);
+    }
+
+    #[test]
+    fn right_click_below_the_drag_threshold_doesnt_pan() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .drag_threshold = 5.0;
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        next_frame(&mut app);
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(2.0, 1.0),
+        });
+        next_frame(&mut app);
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Right);
+        next_frame(&mut app);
+        assert_eq!(
+            app.world.get::<OrbitCamera>(camera).unwrap().focus,
+            Vec3::ZERO
+        );
+    }
+
+    #[test]
+    fn the_gesture_lock_keeps_the_dominant_gesture() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .gesture_lock_window = Some(0.5);
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        next_frame(&mut app);
+
+        // a scrolled line outweighs a small drag, so zooming takes the lock
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        app.world.send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: 1.0,
+            window: Entity::PLACEHOLDER,
+        });
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(5.0, 0.0),
+        });
+        next_frame(&mut app);
+        assert_eq!(
+            app.world.resource::<GestureLock>().gesture,
+            Some(Gesture::Zoom)
+        );
+
+        // the drag is ignored while the lock window lasts
+        for _ in 0..5 {
+            app.world
+                .resource_mut::<ButtonInput<MouseButton>>()
+                .press(MouseButton::Right);
+            app.world.send_event(MouseMotion {
+                delta: Vec2::new(30.0, 0.0),
+            });
+            next_frame(&mut app);
+        }
+        let orbit = app.world.get::<OrbitCamera>(camera).unwrap();
+        assert_eq!(orbit.focus, Vec3::ZERO);
+        assert!(orbit.radius < 5.0);
+    }
+
+    #[test]
+    fn the_default_config_is_valid() {
+        let mut config = OrbitControlsConfig::default();
+        assert!(!config.validate());
+    }
+
+    #[test]
+    fn resizing_keeps_the_vertical_extent() {
+        let projection = OrthographicProjection {
+            scaling_mode: ScalingMode::WindowSize(100.0),
+            ..default()
+        };
+        let previous_size = Vec2::new(800.0, 600.0);
+        let radius =
+            framing_radius_after_resize(&projection, 2.0, previous_size, Vec2::new(800.0, 300.0));
+        // the scale is the radius, so the visible height stays at 6 * 2 world units
+        let height =
+            |radius: f32, size: Vec2| calculate_orthographic_area(&projection, radius, size).y;
+        assert!(
+            (height(radius, Vec2::new(800.0, 300.0)) - height(2.0, previous_size)).abs() < 1e-5
+        );
+        assert!((radius - 4.0).abs() < 1e-5);
+
+        // scaling modes with a fixed height don't need a correction
+        let projection = OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical(2.0),
+            ..default()
+        };
+        let radius =
+            framing_radius_after_resize(&projection, 2.0, previous_size, Vec2::new(400.0, 300.0));
+        assert_eq!(radius, 2.0);
+    }
+
+    #[test]
+    fn yaw_and_pitch_ease_in_at_their_own_rates() {
+        let drag = |yaw_smoothness: f32, pitch_smoothness: f32, frames: usize| {
+            let mut app = controls_app();
+            let mut config = app.world.resource_mut::<OrbitControlsConfig>();
+            config.yaw_smoothness = yaw_smoothness;
+            config.pitch_smoothness = pitch_smoothness;
+            let mut orbit = OrbitCamera::new(Vec3::ZERO, 5.0);
+            orbit.lock_up_axis = true;
+            let camera = spawn_camera(&mut app, orbit);
+            app.world.entity_mut(camera).insert(OrbitAngles::default());
+            next_frame(&mut app);
+
+            app.world
+                .resource_mut::<ButtonInput<MouseButton>>()
+                .press(MouseButton::Left);
+            app.world.send_event(MouseMotion {
+                delta: Vec2::new(-48.0, -48.0),
+            });
+            next_frame(&mut app);
+            for _ in 1..frames {
+                next_frame(&mut app);
+            }
+            *app.world.get::<OrbitAngles>(camera).unwrap()
+        };
+
+        let direct = drag(0.0, 0.0, 1);
+        let first = drag(0.9, 0.5, 1);
+        assert!((first.yaw - direct.yaw * 0.1).abs() < 1e-5);
+        assert!((first.pitch - direct.pitch * 0.5).abs() < 1e-5);
+        let settled = drag(0.9, 0.5, 300);
+        assert!((settled.yaw - direct.yaw).abs() < 1e-4);
+        assert!((settled.pitch - direct.pitch).abs() < 1e-4);
+    }
+
+    #[test]
+    fn scrolling_with_the_cursor_outside_the_viewport_is_ignored() {
+        let radius_after_scroll = |requires_cursor: bool| {
+            let mut app = controls_app();
+            app.world
+                .resource_mut::<OrbitControlsConfig>()
+                .zoom_requires_cursor_in_viewport = requires_cursor;
+            let mut window = Window::default();
+            window.set_cursor_position(Some(Vec2::new(-50.0, -50.0)));
+            app.world.spawn((window, PrimaryWindow));
+            let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+            next_frame(&mut app);
+
+            app.world.send_event(MouseWheel {
+                unit: MouseScrollUnit::Line,
+                x: 0.0,
+                y: 1.0,
+                window: Entity::PLACEHOLDER,
+            });
+            for _ in 0..10 {
+                next_frame(&mut app);
+            }
+            app.world.get::<OrbitCamera>(camera).unwrap().radius
+        };
+
+        assert_eq!(radius_after_scroll(true), 5.0);
+        assert!(radius_after_scroll(false) < 5.0);
+    }
+
+    #[test]
+    fn the_zoom_response_is_monotonic_and_converges() {
+        for smoothness in [0.0, 0.5, 0.9] {
+            let response = sample_zoom_response(smoothness, FRAME_TIME, 200);
+            assert_eq!(response.len(), 200);
+            assert!(response[0] > 0.0);
+            assert!(response.windows(2).all(|pair| pair[1] >= pair[0]));
+            assert!(response.iter().all(|value| *value <= 1.0 + 1e-6));
+            assert!((response[199] - 1.0).abs() < 1e-4);
+        }
+        // a smoother zoom responds more slowly
+        assert!(
+            sample_zoom_response(0.9, FRAME_TIME, 5)[4]
+                < sample_zoom_response(0.5, FRAME_TIME, 5)[4]
+        );
+        assert!(sample_zoom_response(1.0, FRAME_TIME, 10)
+            .iter()
+            .all(|value| *value == 0.0));
+        // halving the sample time reaches the same response in twice the samples
+        let coarse = sample_zoom_response(0.8, FRAME_TIME, 10);
+        let fine = sample_zoom_response(0.8, 0.5 * FRAME_TIME, 20);
+        assert!((coarse[9] - fine[19]).abs() < 1e-4);
+    }
+
+    #[test]
+    fn switching_the_projection_drops_the_remaining_zoom() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .zoom_smoothness = 0.8;
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        next_frame(&mut app);
+        let radius = |app: &App| app.world.get::<OrbitCamera>(camera).unwrap().radius;
+
+        app.world.send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: 3.0,
+            window: Entity::PLACEHOLDER,
+        });
+        next_frame(&mut app);
+        next_frame(&mut app);
+        let zooming = radius(&app);
+        assert!(zooming < 5.0);
+
+        // the distance left to zoom would be a jump in scale after the switch
+        app.world
+            .entity_mut(camera)
+            .insert(Projection::Orthographic(OrthographicProjection::default()));
+        for _ in 0..10 {
+            next_frame(&mut app);
+            assert_eq!(radius(&app), zooming);
+        }
+    }
+
+    #[test]
+    fn a_smoothed_zoom_settles_exactly_once() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .zoom_smoothness = 0.8;
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        let settled = |app: &mut App| {
+            app.world
+                .resource_mut::<Events<CameraSettled>>()
+                .drain()
+                .map(|event| event.0)
+                .collect::<Vec<_>>()
+        };
+        for _ in 0..10 {
+            next_frame(&mut app);
+        }
+        // a camera that never moved doesn't settle
+        assert!(settled(&mut app).is_empty());
+
+        app.world.send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: 1.0,
+            window: Entity::PLACEHOLDER,
+        });
+        next_frame(&mut app);
+        next_frame(&mut app);
+        assert!(settled(&mut app).is_empty());
+        let mut events = Vec::new();
+        for _ in 0..200 {
+            next_frame(&mut app);
+            events.extend(settled(&mut app));
+        }
+        assert_eq!(events, vec![camera]);
+    }
+
+    #[test]
+    fn the_zoom_scope_selects_the_cameras_a_scroll_zooms() {
+        let radii_after_scroll = |zoom_scope: ZoomScope| {
+            let mut app = controls_app();
+            app.world.resource_mut::<OrbitControlsConfig>().zoom_scope = zoom_scope;
+            let cameras = [1, 0].map(|order| {
+                let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+                app.world.get_mut::<Camera>(camera).unwrap().order = order;
+                camera
+            });
+            next_frame(&mut app);
+
+            app.world.send_event(MouseWheel {
+                unit: MouseScrollUnit::Line,
+                x: 0.0,
+                y: 1.0,
+                window: Entity::PLACEHOLDER,
+            });
+            for _ in 0..10 {
+                next_frame(&mut app);
+            }
+            cameras.map(|camera| app.world.get::<OrbitCamera>(camera).unwrap().radius)
+        };
+
+        let [overlay, main] = radii_after_scroll(ZoomScope::All);
+        assert!(overlay < 5.0 && main < 5.0);
+        let [overlay, main] = radii_after_scroll(ZoomScope::Primary);
+        assert!(overlay == 5.0 && main < 5.0);
+        // without a window, the cursor is over neither camera
+        assert_eq!(radii_after_scroll(ZoomScope::Hovered), [5.0, 5.0]);
+    }
+
+    #[test]
+    fn a_flicked_pan_glides_on_and_decays() {
+        let mut app = controls_app();
+        app.world.resource_mut::<OrbitControlsConfig>().pan_inertia = 0.2;
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        next_frame(&mut app);
+        let focus = |app: &App| app.world.get::<OrbitCamera>(camera).unwrap().focus;
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(30.0, 0.0),
+        });
+        next_frame(&mut app);
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Right);
+        let mut previous = focus(&app);
+        let mut steps = Vec::new();
+        for _ in 0..240 {
+            next_frame(&mut app);
+            steps.push(focus(&app).distance(previous));
+            previous = focus(&app);
+        }
+        assert!(steps[0] > 1e-3);
+        assert!(steps.windows(2).all(|pair| pair[1] <= pair[0] + 1e-7));
+        // the glide stops once it's slow enough
+        assert_eq!(steps[239], 0.0);
+    }
+
+    #[test]
+    fn a_pan_glide_stops_at_the_pan_rect() {
+        let mut app = controls_app();
+        app.world.resource_mut::<OrbitControlsConfig>().pan_inertia = 0.2;
+        let mut orbit = OrbitCamera::new(Vec3::ZERO, 5.0);
+        orbit.pan_rect = Some(Rect::new(-0.1, -0.1, 0.1, 0.1));
+        let camera = spawn_camera(&mut app, orbit);
+        next_frame(&mut app);
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(30.0, 0.0),
+        });
+        next_frame(&mut app);
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Right);
+        for _ in 0..10 {
+            next_frame(&mut app);
+        }
+        assert_eq!(
+            app.world.get::<TargetPan>(camera).unwrap().velocity,
+            Vec2::ZERO
+        );
+        let focus = app.world.get::<OrbitCamera>(camera).unwrap().focus;
+        assert!(focus.x.abs() <= 0.1 + 1e-5 && focus.y.abs() <= 0.1 + 1e-5);
+    }
+
+    #[test]
+    fn dragging_with_the_roll_combination_rolls_by_the_horizontal_motion() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .roll_drag_button = Some((Some(KeyCode::AltLeft), MouseButton::Middle));
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world.entity_mut(camera).insert(OrbitAngles::default());
+        next_frame(&mut app);
+        let angles = |app: &App| *app.world.get::<OrbitAngles>(camera).unwrap();
+
+        // 60 pixels of the 600 pixel high viewport roll by a tenth of the roll speed
+        let mut rolls = Vec::new();
+        for delta in [Vec2::new(60.0, 0.0), Vec2::new(60.0, 40.0)] {
+            app.world
+                .resource_mut::<ButtonInput<KeyCode>>()
+                .press(KeyCode::AltLeft);
+            app.world
+                .resource_mut::<ButtonInput<MouseButton>>()
+                .press(MouseButton::Middle);
+            app.world.send_event(MouseMotion { delta });
+            next_frame(&mut app);
+            rolls.push(angles(&app).roll.abs());
+        }
+        assert!((rolls[0] - PI / 10.0).abs() < 1e-4);
+        assert!((rolls[1] - PI / 5.0).abs() < 1e-4);
+        // the vertical motion doesn't orbit
+        assert!(angles(&app).yaw.abs() < 1e-6 && angles(&app).pitch.abs() < 1e-6);
+    }
+
+    #[test]
+    fn input_on_the_spawn_frame_is_skipped() {
+        let mut app = controls_app();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(100.0, 50.0),
+        });
+        app.world.send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: 5.0,
+            window: Entity::PLACEHOLDER,
+        });
+        next_frame(&mut app);
+
+        let orbit = app.world.get::<OrbitCamera>(camera).unwrap();
+        let transform = app.world.get::<Transform>(camera).unwrap();
+        assert_eq!(orbit.radius, 5.0);
+        assert_eq!(orbit.focus, Vec3::ZERO);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(0.0, 0.0, 5.0), 1e-6));
+        assert!(transform.rotation.abs_diff_eq(Quat::IDENTITY, 1e-6));
+    }
+
+    #[test]
+    fn an_underdamped_spring_overshoots_and_a_critically_damped_one_doesnt() {
+        let radii = |damping: f32| {
+            let mut app = controls_app();
+            app.world
+                .resource_mut::<OrbitControlsConfig>()
+                .smoothing_model = SmoothingModel::Spring {
+                stiffness: 100.0,
+                damping,
+            };
+            let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+            next_frame(&mut app);
+
+            app.world
+                .get_mut::<OrbitCamera>(camera)
+                .unwrap()
+                .set_radius_smoothed(2.0);
+            (0..300)
+                .map(|_| {
+                    next_frame(&mut app);
+                    app.world.get::<OrbitCamera>(camera).unwrap().radius
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let underdamped = radii(4.0);
+        assert!(underdamped.iter().any(|radius| *radius < 1.9));
+        assert!((underdamped[299] - 2.0).abs() < 1e-3);
+        let critically_damped = radii(20.0);
+        assert!(critically_damped.iter().all(|radius| *radius > 2.0 - 1e-4));
+        assert!((critically_damped[299] - 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn holding_a_zoom_axis_key_ramps_the_zoom_up_and_down() {
+        let mut app = controls_app();
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .zoom_axis_keys = Some((KeyCode::Equal, KeyCode::Minus));
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        next_frame(&mut app);
+        // the zoom rate of a frame in natural-log units per second
+        let mut radius = 5.0;
+        let mut rate = |app: &App| {
+            let next = app.world.get::<OrbitCamera>(camera).unwrap().radius;
+            let rate = (radius / next).ln() / FRAME_TIME;
+            radius = next;
+            rate
+        };
+
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Equal);
+        let mut pressed = Vec::new();
+        for _ in 0..30 {
+            next_frame(&mut app);
+            pressed.push(rate(&app));
+        }
+        // ramps up over `zoom_axis_ramp`, then holds `zoom_axis_speed`
+        assert!(pressed[0] > 0.0 && pressed[0] < 0.1);
+        assert!(pressed[..18].windows(2).all(|pair| pair[1] > pair[0]));
+        assert!((pressed[29] - 1.0).abs() < 1e-2);
+
+        app.world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::Equal);
+        let mut released = Vec::new();
+        for _ in 0..30 {
+            next_frame(&mut app);
+            released.push(rate(&app));
         }
+        assert!(released[0] > 0.5 && released[0] < 1.0);
+        assert!(released[..18].windows(2).all(|pair| pair[1] <= pair[0]));
+        assert_eq!(released[29], 0.0);
     }
 }
@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+
+use crate::{OrbitAngles, OrbitCamera, OrbitCameraSystemSet};
+
+/// Plugin showing live stats of the primary orbit camera in a text overlay, useful for tuning.
+///
+/// The primary camera is the active `OrbitCamera` with the lowest order.
+#[derive(Debug, Clone, Default)]
+pub struct OrbitDebugOverlayPlugin;
+
+impl Plugin for OrbitDebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_overlay)
+            .add_systems(PostUpdate, update_overlay.after(OrbitCameraSystemSet));
+    }
+}
+
+/// Marker component for the text of the debug overlay.
+#[derive(Component)]
+pub struct OrbitDebugOverlay;
+
+fn spawn_overlay(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 16.0,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            right: Val::Px(8.0),
+            top: Val::Px(8.0),
+            ..default()
+        }),
+        OrbitDebugOverlay,
+    ));
+}
+
+fn update_overlay(
+    camera_q: Query<(&OrbitCamera, &Transform, &Camera, &Projection)>,
+    mut text_q: Query<&mut Text, With<OrbitDebugOverlay>>,
+) {
+    let Some((property, transform, _, projection)) = camera_q
+        .iter()
+        .filter(|(_, _, camera, _)| camera.is_active)
+        .min_by_key(|(_, _, camera, _)| camera.order)
+    else {
+        return;
+    };
+    let up_axis = property.up_axis.unwrap_or_default();
+    let angles = OrbitAngles::from_rotation(up_axis.basis().inverse() * transform.rotation);
+    let projection = match projection {
+        Projection::Perspective(p) => format!("fov: {:.1}°", p.fov.to_degrees()),
+        Projection::Orthographic(p) => format!("scale: {:.3}", p.scale),
+    };
+    let stats = format!(
+        "focus: {:.2}\nradius: {:.3}\nyaw: {:.1}°\npitch: {:.1}°\nroll: {:.1}°\n{projection}",
+        property.focus,
+        property.radius,
+        angles.yaw.to_degrees(),
+        angles.pitch.to_degrees(),
+        angles.roll.to_degrees(),
+    );
+    for mut text in text_q.iter_mut() {
+        if let Some(section) = text.sections.first_mut() {
+            section.value.clone_from(&stats);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{spawn_camera, test_app};
+
+    #[test]
+    fn overlay_shows_the_stats_of_the_primary_camera() {
+        let mut app = test_app();
+        app.add_plugins(OrbitDebugOverlayPlugin);
+        spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        let secondary = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 2.0));
+        app.world.get_mut::<Camera>(secondary).unwrap().order = 1;
+        app.update();
+
+        let text = app
+            .world
+            .query_filtered::<&Text, With<OrbitDebugOverlay>>()
+            .single(&app.world);
+        let stats = &text.sections[0].value;
+        assert!(stats.contains("radius: 5.000"));
+        assert!(stats.contains("fov: 45.0°"));
+    }
+}
@@ -0,0 +1,73 @@
+use bevy::{
+    core_pipeline::prepass::DepthPrepass, prelude::*, render::camera::CameraProjection,
+    utils::HashSet,
+};
+
+use crate::controls::{OrbitControlsSystemSet, ScreenCenterHit};
+
+/// Plugin turning the depth at the screen center into the [`ScreenCenterHit`] of cameras with [`DepthAutoFocus`],
+/// so `OrbitControlsConfig::auto_focus_screen_center` works without a physics or picking dependency.
+///
+/// Copying the depth prepass texture back to the CPU isn't done by this plugin yet. Until it is, the depth is read
+/// from `DepthAutoFocus::depth`, which a render-world readback of the app has to write.
+#[derive(Debug, Clone, Default)]
+pub struct DepthAutoFocusPlugin;
+
+impl Plugin for DepthAutoFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, depth_auto_focus.before(OrbitControlsSystemSet));
+    }
+}
+
+/// Component enabling the automatic focus depth of [`DepthAutoFocusPlugin`] on a camera.
+///
+/// The camera needs a `DepthPrepass` and a [`ScreenCenterHit`], otherwise the component does nothing
+/// and a warning is logged once.
+#[derive(Debug, Clone, Copy, Component, Default)]
+pub struct DepthAutoFocus {
+    /// The normalized device depth at the screen center, `1.0` at the near plane and `0.0` at infinity,
+    /// or `None` while no depth has been read back.
+    pub depth: Option<f32>,
+}
+
+type DepthAutoFocusQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static Projection,
+        &'static DepthAutoFocus,
+        Option<&'static mut ScreenCenterHit>,
+        Has<DepthPrepass>,
+    ),
+>;
+
+fn depth_auto_focus(mut camera_q: DepthAutoFocusQuery, mut warned: Local<HashSet<Entity>>) {
+    for (entity, transform, projection, auto_focus, hit, has_prepass) in camera_q.iter_mut() {
+        let Some(mut hit) = hit.filter(|_| has_prepass) else {
+            if warned.insert(entity) {
+                warn!("DepthAutoFocus on {entity:?} needs a DepthPrepass and a ScreenCenterHit, ignoring it");
+            }
+            continue;
+        };
+        hit.0 = auto_focus
+            .depth
+            .and_then(|depth| center_depth_to_world(transform, projection, depth));
+    }
+}
+
+/// Unprojects a normalized device depth at the screen center into world space, `None` for the far plane.
+fn center_depth_to_world(
+    transform: &Transform,
+    projection: &Projection,
+    depth: f32,
+) -> Option<Vec3> {
+    // the reversed depth of bevy puts nothing at zero
+    if depth <= 0.0 || !depth.is_finite() {
+        return None;
+    }
+    let ndc_to_world = transform.compute_matrix() * projection.get_projection_matrix().inverse();
+    let point = ndc_to_world.project_point3(Vec3::new(0.0, 0.0, depth));
+    point.is_finite().then_some(point)
+}
@@ -0,0 +1,782 @@
+use bevy::prelude::*;
+
+use crate::{
+    time::{OrbitTime, TimeSourceOverride},
+    util::reflect_transform,
+    OrbitCamera,
+};
+
+/// Component that keeps the `OrbitCamera` focus on the position of another entity.
+///
+/// The focus is low-pass filtered toward the target so jitter from e.g. physics steps isn't passed on to the camera.
+/// Panning moves the focus relative to the target through `follow_pan_offset` instead of fighting the follow.
+#[derive(Debug, Clone, Component)]
+pub struct FollowTarget {
+    /// The entity to follow.
+    pub target: Entity,
+    /// The time in seconds for the focus to close ~63% of the distance to the target. Set to `0.0` to snap.
+    pub follow_smoothness: f32,
+    /// The offset of the focus from the target in world space, accumulated from the pan of the camera.
+    pub follow_pan_offset: Vec3,
+}
+
+impl FollowTarget {
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            follow_smoothness: 0.0,
+            follow_pan_offset: Vec3::ZERO,
+        }
+    }
+
+    pub fn with_pan_offset(mut self, follow_pan_offset: Vec3) -> Self {
+        self.follow_pan_offset = follow_pan_offset;
+        self
+    }
+
+    pub fn with_smoothness(mut self, follow_smoothness: f32) -> Self {
+        self.follow_smoothness = follow_smoothness;
+        self
+    }
+
+    /// Moves `focus` toward `target` using frame-rate independent exponential smoothing.
+    pub fn smooth_focus(&self, focus: Vec3, target: Vec3, delta_seconds: f32) -> Vec3 {
+        smooth_toward(focus, target, self.follow_smoothness, delta_seconds)
+    }
+}
+
+fn smooth_toward(focus: Vec3, target: Vec3, smoothness: f32, delta_seconds: f32) -> Vec3 {
+    if smoothness <= 0.0 {
+        return target;
+    }
+    let t = 1.0 - (-delta_seconds / smoothness).exp();
+    let focus = focus.lerp(target, t);
+    // snap once close enough so a resting target doesn't leave a permanent lag
+    if focus.distance_squared(target) < 1e-8 {
+        target
+    } else {
+        focus
+    }
+}
+
+pub(crate) fn follow_target(
+    time: OrbitTime,
+    mut camera_q: Query<(
+        &mut OrbitCamera,
+        &Transform,
+        &mut FollowTarget,
+        Option<&TimeSourceOverride>,
+    )>,
+    target_q: Query<&GlobalTransform>,
+) {
+    for (mut property, transform, mut follow, source_override) in camera_q.iter_mut() {
+        let Ok(target) = target_q.get(follow.target) else {
+            continue;
+        };
+        // the pan sticks as an offset from the moving target, like `update_transform` would apply it
+        if property.pan != Vec2::ZERO {
+            follow.follow_pan_offset += transform.rotation * property.pan.extend(0.0);
+            property.pan = Vec2::ZERO;
+        }
+        property.focus = follow.smooth_focus(
+            property.focus,
+            target.translation() + follow.follow_pan_offset,
+            time.delta_seconds_for(source_override),
+        );
+    }
+}
+
+/// Component that keeps the `OrbitCamera` focus on the centroid of a set of entities, e.g. an editor selection.
+///
+/// The targets can be changed at any time. Despawned targets are skipped and an empty set leaves the focus as is.
+#[derive(Debug, Clone, Component, Default)]
+pub struct FocusCentroid {
+    pub targets: Vec<Entity>,
+    /// The time in seconds for the focus to close ~63% of the distance to the centroid. Set to `0.0` to snap.
+    pub follow_smoothness: f32,
+}
+
+impl FocusCentroid {
+    pub fn new(targets: Vec<Entity>) -> Self {
+        Self {
+            targets,
+            follow_smoothness: 0.0,
+        }
+    }
+
+    pub fn with_smoothness(mut self, follow_smoothness: f32) -> Self {
+        self.follow_smoothness = follow_smoothness;
+        self
+    }
+}
+
+pub(crate) fn follow_centroid(
+    time: OrbitTime,
+    mut camera_q: Query<(
+        &mut OrbitCamera,
+        &FocusCentroid,
+        Option<&TimeSourceOverride>,
+    )>,
+    target_q: Query<&GlobalTransform>,
+) {
+    for (mut property, centroid, source_override) in camera_q.iter_mut() {
+        let (sum, count) = target_q
+            .iter_many(&centroid.targets)
+            .fold((Vec3::ZERO, 0), |(sum, count), target| {
+                (sum + target.translation(), count + 1)
+            });
+        if count == 0 {
+            continue;
+        }
+        property.focus = smooth_toward(
+            property.focus,
+            sum / count as f32,
+            centroid.follow_smoothness,
+            time.delta_seconds_for(source_override),
+        );
+    }
+}
+
+/// Component that moves the `OrbitCamera` focus along a path of waypoints, e.g. for guided tours.
+///
+/// Only the focus is driven, so orbiting and zooming stay under user control.
+#[derive(Debug, Clone, Component)]
+pub struct FocusPath {
+    pub points: Vec<Vec3>,
+    /// The position along the path in waypoints, e.g. `1.5` is halfway between the second and third point.
+    pub t: f32,
+    /// The speed in waypoints per second.
+    pub speed: f32,
+    /// Continues from the last point back to the first instead of stopping at the end.
+    pub looping: bool,
+    /// Interpolates with a Catmull-Rom spline through the points instead of straight lines.
+    pub smooth: bool,
+}
+
+impl FocusPath {
+    pub fn new(points: Vec<Vec3>, speed: f32) -> Self {
+        Self {
+            points,
+            t: 0.0,
+            speed,
+            looping: false,
+            smooth: false,
+        }
+    }
+
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    pub fn with_smooth(mut self, smooth: bool) -> Self {
+        self.smooth = smooth;
+        self
+    }
+
+    /// The length of the path in waypoints, i.e. the largest value of `t`.
+    pub fn length(&self) -> f32 {
+        let segments = if self.looping {
+            self.points.len()
+        } else {
+            self.points.len().saturating_sub(1)
+        };
+        segments as f32
+    }
+
+    /// Whether the end of a non-looping path has been reached.
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.t >= self.length()
+    }
+
+    /// Advances `t` by `speed`, wrapping around for looping paths and stopping at the end otherwise.
+    pub fn advance(&mut self, delta_seconds: f32) {
+        let length = self.length();
+        self.t += self.speed * delta_seconds;
+        self.t = if self.looping && length > 0.0 {
+            self.t.rem_euclid(length)
+        } else {
+            self.t.clamp(0.0, length)
+        };
+    }
+
+    /// The point on the path at `t`, or `None` if the path has no points.
+    pub fn sample(&self, t: f32) -> Option<Vec3> {
+        let count = self.points.len();
+        if count == 0 {
+            return None;
+        }
+        let t = t.clamp(0.0, self.length());
+        let segment = (t.floor() as usize).min(self.length().max(1.0) as usize - 1);
+        let fraction = t - segment as f32;
+        let point = |index: isize| {
+            let index = if self.looping {
+                index.rem_euclid(count as isize)
+            } else {
+                index.clamp(0, count as isize - 1)
+            };
+            self.points[index as usize]
+        };
+        let index = segment as isize;
+        let (p1, p2) = (point(index), point(index + 1));
+        if !self.smooth {
+            return Some(p1.lerp(p2, fraction));
+        }
+        let (p0, p3) = (point(index - 1), point(index + 2));
+        let (t2, t3) = (fraction * fraction, fraction * fraction * fraction);
+        Some(
+            0.5 * (2.0 * p1
+                + (p2 - p0) * fraction
+                + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+                + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3),
+        )
+    }
+}
+
+pub(crate) fn follow_path(
+    time: OrbitTime,
+    mut camera_q: Query<(
+        &mut OrbitCamera,
+        &mut FocusPath,
+        Option<&TimeSourceOverride>,
+    )>,
+) {
+    for (mut property, mut path, source_override) in camera_q.iter_mut() {
+        path.advance(time.delta_seconds_for(source_override));
+        if let Some(point) = path.sample(path.t) {
+            property.focus = point;
+        }
+    }
+}
+
+/// Component that eases the roll of an `OrbitCamera` toward the up vector of another entity.
+///
+/// Unlike [`FollowTarget`], which follows the position, this syncs the camera banking with the target.
+#[derive(Debug, Clone, Component)]
+pub struct MatchTargetRoll {
+    /// The entity whose up vector is matched.
+    pub target: Entity,
+    /// The fraction of the remaining roll difference applied each update, in the range `0.0..=1.0`.
+    pub weight: f32,
+}
+
+impl MatchTargetRoll {
+    pub fn new(target: Entity, weight: f32) -> Self {
+        Self { target, weight }
+    }
+}
+
+/// Calculates the signed roll angle around the view axis that aligns the camera up with `target_up`.
+pub fn roll_to_match(rotation: Quat, target_up: Vec3) -> f32 {
+    let back = rotation * Vec3::Z;
+    let up = rotation * Vec3::Y;
+    let target_up = target_up.reject_from(back);
+    if target_up.length_squared() < 1e-8 {
+        return 0.0;
+    }
+    f32::atan2(back.dot(up.cross(target_up)), up.dot(target_up))
+}
+
+pub(crate) fn match_target_roll(
+    mut camera_q: Query<(&mut OrbitCamera, &Transform, &MatchTargetRoll)>,
+    target_q: Query<&GlobalTransform>,
+) {
+    for (mut property, transform, match_roll) in camera_q.iter_mut() {
+        // skip silently if the target has been despawned
+        let Ok(target) = target_q.get(match_roll.target) else {
+            continue;
+        };
+        let angle = roll_to_match(transform.rotation, target.up());
+        property.roll(match_roll.weight.clamp(0.0, 1.0) * angle);
+    }
+}
+
+/// Velocity of a followed entity, supplied by the user, e.g. copied from a physics engine.
+#[derive(Debug, Clone, Copy, Component, Default)]
+pub struct TargetVelocity(pub Vec3);
+
+/// Component that widens the field of view of a perspective `OrbitCamera` with the speed of its [`FollowTarget`].
+///
+/// The speed is read from the [`TargetVelocity`] of the target.
+#[derive(Debug, Clone, Component)]
+pub struct SpeedFov {
+    /// The field of view in radians when the target is at rest.
+    pub min_fov: f32,
+    /// The field of view in radians when the target moves at `max_speed` or faster.
+    pub max_fov: f32,
+    pub max_speed: f32,
+    /// The time in seconds for the field of view to close ~63% of the distance to its target. Set to `0.0` to snap.
+    pub smoothness: f32,
+}
+
+impl SpeedFov {
+    pub fn new(min_fov: f32, max_fov: f32, max_speed: f32) -> Self {
+        Self {
+            min_fov,
+            max_fov,
+            max_speed,
+            smoothness: 0.0,
+        }
+    }
+
+    /// Maps a speed to the field of view, clamped to the configured range.
+    pub fn fov_for_speed(&self, speed: f32) -> f32 {
+        let t = if self.max_speed > 0.0 {
+            (speed / self.max_speed).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        f32::lerp(self.min_fov, self.max_fov, t)
+    }
+}
+
+pub(crate) fn speed_fov(
+    time: OrbitTime,
+    mut camera_q: Query<(
+        &mut Projection,
+        &SpeedFov,
+        &FollowTarget,
+        Option<&TimeSourceOverride>,
+    )>,
+    target_q: Query<&TargetVelocity>,
+) {
+    for (mut projection, speed_fov, follow, source_override) in camera_q.iter_mut() {
+        let Projection::Perspective(ref mut p) = *projection else {
+            continue;
+        };
+        let speed = target_q
+            .get(follow.target)
+            .map_or(0.0, |velocity| velocity.0.length());
+        let fov = speed_fov.fov_for_speed(speed);
+        p.fov = if speed_fov.smoothness > 0.0 {
+            let delta_seconds = time.delta_seconds_for(source_override);
+            let t = 1.0 - (-delta_seconds / speed_fov.smoothness).exp();
+            f32::lerp(p.fov, fov, t)
+        } else {
+            fov
+        };
+    }
+}
+
+/// Component for a light entity that keeps it at the transform of an `OrbitCamera`, e.g. as a headlight.
+///
+/// The light isn't required to be a child of the camera. A light that is a child of the camera keeps an identity
+/// transform, since it inherits the transform of the camera already. It is updated right after the camera transform
+/// is computed.
+#[derive(Debug, Clone, Component)]
+pub struct CameraHeadlight {
+    /// The camera entity to follow.
+    pub camera: Entity,
+}
+
+impl CameraHeadlight {
+    pub fn new(camera: Entity) -> Self {
+        Self { camera }
+    }
+}
+
+pub(crate) fn update_headlight(
+    camera_q: Query<&Transform, (With<OrbitCamera>, Without<CameraHeadlight>)>,
+    mut light_q: Query<(&mut Transform, &CameraHeadlight, Option<&Parent>)>,
+) {
+    for (mut transform, headlight, parent) in light_q.iter_mut() {
+        if parent.is_some_and(|parent| parent.get() == headlight.camera) {
+            *transform = Transform::IDENTITY;
+        } else if let Ok(camera_transform) = camera_q.get(headlight.camera) {
+            *transform = *camera_transform;
+        }
+    }
+}
+
+/// Component for a camera that mirrors the transform of an `OrbitCamera` across a plane, e.g. for reflections.
+#[derive(Debug, Clone, Component)]
+pub struct ReflectionCamera {
+    /// The camera entity to mirror.
+    pub source: Entity,
+    /// Any point on the mirror plane.
+    pub plane_point: Vec3,
+    /// The normal of the mirror plane.
+    pub plane_normal: Vec3,
+}
+
+impl ReflectionCamera {
+    pub fn new(source: Entity, plane_point: Vec3, plane_normal: Vec3) -> Self {
+        Self {
+            source,
+            plane_point,
+            plane_normal,
+        }
+    }
+}
+
+pub(crate) fn update_reflection(
+    camera_q: Query<&Transform, (With<OrbitCamera>, Without<ReflectionCamera>)>,
+    mut reflection_q: Query<(&mut Transform, &ReflectionCamera)>,
+) {
+    for (mut transform, reflection) in reflection_q.iter_mut() {
+        if let Ok(source_transform) = camera_q.get(reflection.source) {
+            *transform = reflect_transform(
+                source_transform,
+                reflection.plane_point,
+                reflection.plane_normal,
+            );
+        }
+    }
+}
+
+/// Component that keeps another entity on screen while the user orbits, e.g. an enemy in an over-the-shoulder view.
+///
+/// When the target would leave the view, the yaw and pitch are nudged toward it, so this is a soft constraint
+/// on top of user control. Only perspective cameras are supported.
+#[derive(Debug, Clone, Component)]
+pub struct KeepInView {
+    /// The entity to keep in view.
+    pub target: Entity,
+    /// The margin kept at the edges of the view, as a fraction in the range `0.0..1.0` of the half field of view.
+    pub margin: f32,
+}
+
+impl KeepInView {
+    pub fn new(target: Entity, margin: f32) -> Self {
+        Self { target, margin }
+    }
+}
+
+pub(crate) fn keep_in_view(
+    mut camera_q: Query<(&mut OrbitCamera, &Transform, &Projection, &KeepInView)>,
+    target_q: Query<&GlobalTransform>,
+) {
+    for (mut property, transform, projection, keep_in_view) in camera_q.iter_mut() {
+        let Projection::Perspective(p) = projection else {
+            continue;
+        };
+        let Ok(target) = target_q.get(keep_in_view.target) else {
+            continue;
+        };
+        let pose = property.pending_pose(transform, projection);
+        let translation = pose.focus + pose.rotation * Vec3::new(0.0, 0.0, pose.radius);
+        let local = pose.rotation.inverse() * (target.translation() - translation);
+        if local.z >= 0.0 {
+            // behind the camera, there is no minimal nudge
+            continue;
+        }
+        let allowed = 1.0 - keep_in_view.margin.clamp(0.0, 1.0);
+        let tan_half_fov = (0.5 * p.fov).tan();
+        let max_angle = Vec2::new(
+            (allowed * tan_half_fov * p.aspect_ratio).atan(),
+            (allowed * tan_half_fov).atan(),
+        );
+        let angle = Vec2::new((local.x / -local.z).atan(), (local.y / -local.z).atan());
+        let excess = angle - angle.clamp(-max_angle, max_angle);
+        // turning right is a negative yaw, looking up a negative pitch delta
+        property.orbit(-excess.x, -excess.y, 0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{spawn_camera, test_app};
+
+    #[test]
+    fn smoothed_follow_filters_target_jitter() {
+        let follow = FollowTarget::new(Entity::PLACEHOLDER).with_smoothness(0.2);
+        // a resting target jittering back and forth every physics step
+        let targets: Vec<Vec3> = (0..120)
+            .map(|i| Vec3::X * if i % 2 == 0 { 0.05 } else { -0.05 })
+            .collect();
+        let mut focus = Vec3::ZERO;
+        let focuses: Vec<Vec3> = targets
+            .iter()
+            .map(|target| {
+                focus = follow.smooth_focus(focus, *target, 1.0 / 60.0);
+                focus
+            })
+            .collect();
+        let variance = |points: &[Vec3]| {
+            let mean = points.iter().sum::<Vec3>() / points.len() as f32;
+            points
+                .iter()
+                .map(|point| point.distance_squared(mean))
+                .sum::<f32>()
+                / points.len() as f32
+        };
+        assert!(variance(&focuses) < 0.1 * variance(&targets));
+    }
+
+    #[test]
+    fn smoothed_follow_settles_on_a_resting_target() {
+        let follow = FollowTarget::new(Entity::PLACEHOLDER).with_smoothness(0.2);
+        let target = Vec3::new(1.0, 2.0, 3.0);
+        let mut focus = Vec3::ZERO;
+        for _ in 0..600 {
+            focus = follow.smooth_focus(focus, target, 1.0 / 60.0);
+        }
+        assert_eq!(focus, target);
+    }
+
+    #[test]
+    fn camera_roll_follows_the_target_up_by_the_weight() {
+        let mut app = test_app();
+        let target = app
+            .world
+            .spawn(GlobalTransform::from(Transform::from_rotation(
+                Quat::from_rotation_z(0.4),
+            )))
+            .id();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world
+            .entity_mut(camera)
+            .insert(MatchTargetRoll::new(target, 0.5));
+        let target_up = Quat::from_rotation_z(0.4) * Vec3::Y;
+        let remaining = |app: &App| {
+            roll_to_match(
+                app.world.get::<Transform>(camera).unwrap().rotation,
+                target_up,
+            )
+        };
+
+        app.update();
+        app.update();
+        // two updates leave a quarter of the roll of the target
+        let mut before = remaining(&app);
+        assert!((before.abs() - 0.4 * 0.25).abs() < 1e-4);
+        for _ in 0..3 {
+            app.update();
+            let after = remaining(&app);
+            assert!((after - 0.5 * before).abs() < 1e-4);
+            before = after;
+        }
+    }
+
+    #[test]
+    fn matching_the_roll_of_a_despawned_target_keeps_the_roll() {
+        let mut app = test_app();
+        let target = app.world.spawn(GlobalTransform::default()).id();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world
+            .entity_mut(camera)
+            .insert(MatchTargetRoll::new(target, 1.0));
+        app.update();
+        app.world.despawn(target);
+        let rotation = app.world.get::<Transform>(camera).unwrap().rotation;
+        app.update();
+        let after = app.world.get::<Transform>(camera).unwrap().rotation;
+        assert!(after.abs_diff_eq(rotation, 1e-6));
+    }
+
+    #[test]
+    fn field_of_view_interpolates_with_the_target_speed() {
+        let mut app = test_app();
+        let target = app
+            .world
+            .spawn((GlobalTransform::default(), TargetVelocity(Vec3::ZERO)))
+            .id();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world
+            .entity_mut(camera)
+            .insert((FollowTarget::new(target), SpeedFov::new(0.8, 1.2, 10.0)));
+        let fov_at = |app: &mut App, speed: f32| {
+            app.world.get_mut::<TargetVelocity>(target).unwrap().0 = Vec3::new(0.0, 0.0, speed);
+            app.update();
+            match app.world.get::<Projection>(camera).unwrap() {
+                Projection::Perspective(p) => p.fov,
+                Projection::Orthographic(_) => unreachable!(),
+            }
+        };
+
+        assert!((fov_at(&mut app, 2.5) - 0.9).abs() < 1e-5);
+        assert!((fov_at(&mut app, 7.5) - 1.1).abs() < 1e-5);
+        // clamped to the configured range
+        assert!((fov_at(&mut app, 30.0) - 1.2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn headlight_matches_the_camera_transform() {
+        let mut app = test_app();
+        let camera = spawn_camera(
+            &mut app,
+            OrbitCamera::new(Vec3::Y, 5.0).with_orbit(0.4, 0.2, 0.0),
+        );
+        let light = app
+            .world
+            .spawn((Transform::default(), CameraHeadlight::new(camera)))
+            .id();
+        app.update();
+        let mut orbit = app.world.get_mut::<OrbitCamera>(camera).unwrap();
+        orbit.orbit(0.3, -0.1, 0.0);
+        orbit.pan(Vec2::new(1.0, 0.5));
+        app.update();
+
+        let camera_transform = app.world.get::<Transform>(camera).unwrap();
+        assert_eq!(app.world.get::<Transform>(light).unwrap(), camera_transform);
+    }
+
+    #[test]
+    fn child_headlight_keeps_an_identity_transform() {
+        let mut app = test_app();
+        let camera = spawn_camera(
+            &mut app,
+            OrbitCamera::new(Vec3::Y, 5.0).with_orbit(0.4, 0.2, 0.0),
+        );
+        let light = app
+            .world
+            .spawn((
+                Transform::from_xyz(1.0, 2.0, 3.0),
+                CameraHeadlight::new(camera),
+            ))
+            .set_parent(camera)
+            .id();
+        app.update();
+
+        assert_eq!(
+            *app.world.get::<Transform>(light).unwrap(),
+            Transform::IDENTITY
+        );
+    }
+
+    #[test]
+    fn focus_path_interpolates_between_two_points() {
+        let (start, end) = (Vec3::new(0.0, 0.0, 0.0), Vec3::new(4.0, 2.0, 0.0));
+        for path in [
+            FocusPath::new(vec![start, end], 0.5),
+            FocusPath::new(vec![start, end], 0.5).with_smooth(true),
+        ] {
+            assert!(path.sample(0.0).unwrap().abs_diff_eq(start, 1e-6));
+            assert!(path
+                .sample(0.5)
+                .unwrap()
+                .abs_diff_eq(Vec3::new(2.0, 1.0, 0.0), 1e-5));
+            assert!(path.sample(1.0).unwrap().abs_diff_eq(end, 1e-6));
+            // the spline through two points eases along the same straight line
+            assert!(path.sample(0.25).unwrap().cross(end).length() < 1e-5);
+            // clamped past the end of a path that doesn't loop
+            assert!(path.sample(3.0).unwrap().abs_diff_eq(end, 1e-6));
+        }
+        let linear = FocusPath::new(vec![start, end], 0.5);
+        let quarter = linear.sample(0.25).unwrap();
+        assert!(quarter.abs_diff_eq(Vec3::new(1.0, 0.5, 0.0), 1e-5));
+        assert!(FocusPath::new(Vec::new(), 1.0).sample(0.0).is_none());
+    }
+
+    #[test]
+    fn focus_follows_the_path_over_time() {
+        let mut app = test_app();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world.entity_mut(camera).insert(FocusPath::new(
+            vec![Vec3::ZERO, Vec3::new(6.0, 0.0, 0.0)],
+            1.0,
+        ));
+        // the first frame has a time step of zero
+        for _ in 0..31 {
+            app.update();
+        }
+        let focus = app.world.get::<OrbitCamera>(camera).unwrap().focus;
+        assert!(focus.abs_diff_eq(Vec3::new(3.0, 0.0, 0.0), 1e-3));
+        assert!(!app.world.get::<FocusPath>(camera).unwrap().is_finished());
+    }
+
+    #[test]
+    fn focus_follows_the_centroid_of_the_targets() {
+        let mut app = test_app();
+        let targets: Vec<Entity> = [
+            Vec3::ZERO,
+            Vec3::new(3.0, 0.0, 0.0),
+            Vec3::new(0.0, 6.0, 3.0),
+        ]
+        .into_iter()
+        .map(|position| {
+            app.world
+                .spawn(GlobalTransform::from_translation(position))
+                .id()
+        })
+        .collect();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::splat(-5.0), 5.0));
+        app.world
+            .entity_mut(camera)
+            .insert(FocusCentroid::new(targets.clone()));
+        let focus = |app: &App| app.world.get::<OrbitCamera>(camera).unwrap().focus;
+
+        app.update();
+        assert!(focus(&app).abs_diff_eq(Vec3::new(1.0, 2.0, 1.0), 1e-5));
+
+        app.world.despawn(targets[2]);
+        app.update();
+        assert!(focus(&app).abs_diff_eq(Vec3::new(1.5, 0.0, 0.0), 1e-5));
+
+        app.world
+            .get_mut::<FocusCentroid>(camera)
+            .unwrap()
+            .targets
+            .clear();
+        app.update();
+        assert!(focus(&app).abs_diff_eq(Vec3::new(1.5, 0.0, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn keep_in_view_yaws_toward_a_target_leaving_the_view() {
+        let mut app = test_app();
+        let target = app
+            .world
+            .spawn(GlobalTransform::from_translation(Vec3::new(1.0, 0.0, 0.0)))
+            .id();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world
+            .entity_mut(camera)
+            .insert(KeepInView::new(target, 0.1));
+        app.update();
+        // well within the view, so the camera isn't nudged
+        let forward = |app: &App| Vec3::from(app.world.get::<Transform>(camera).unwrap().forward());
+        assert!(forward(&app).abs_diff_eq(Vec3::NEG_Z, 1e-6));
+
+        *app.world.get_mut::<GlobalTransform>(target).unwrap() =
+            GlobalTransform::from_translation(Vec3::new(4.0, 0.0, 0.0));
+        for _ in 0..30 {
+            app.update();
+        }
+        // the camera turns right, until the target is at the margin
+        assert!(forward(&app).x > 0.0);
+        let transform = app.world.get::<Transform>(camera).unwrap();
+        let local =
+            transform.rotation.inverse() * (Vec3::new(4.0, 0.0, 0.0) - transform.translation);
+        let Projection::Perspective(p) = app.world.get::<Projection>(camera).unwrap() else {
+            unreachable!();
+        };
+        let max_angle = (0.9 * (0.5 * p.fov).tan() * p.aspect_ratio).atan();
+        assert!(((local.x / -local.z).atan() - max_angle).abs() < 1e-3);
+    }
+
+    #[test]
+    fn the_pan_sticks_as_an_offset_from_the_followed_target() {
+        let mut app = test_app();
+        let target = app
+            .world
+            .spawn(GlobalTransform::from_translation(Vec3::new(1.0, 0.0, 0.0)))
+            .id();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world
+            .entity_mut(camera)
+            .insert(FollowTarget::new(target));
+        app.update();
+
+        // the camera looks down -Z, so the pan is along world X and Y
+        app.world
+            .get_mut::<OrbitCamera>(camera)
+            .unwrap()
+            .pan(Vec2::new(0.0, 2.0));
+        app.update();
+        let focus = |app: &App| app.world.get::<OrbitCamera>(camera).unwrap().focus;
+        assert!(focus(&app).abs_diff_eq(Vec3::new(1.0, 2.0, 0.0), 1e-5));
+
+        for x in [2.0, 3.0, 4.0] {
+            *app.world.get_mut::<GlobalTransform>(target).unwrap() =
+                GlobalTransform::from_translation(Vec3::new(x, 0.0, 0.0));
+            app.update();
+            assert!(focus(&app).abs_diff_eq(Vec3::new(x, 2.0, 0.0), 1e-5));
+        }
+        let follow = app.world.get::<FollowTarget>(camera).unwrap();
+        assert!(follow
+            .follow_pan_offset
+            .abs_diff_eq(Vec3::new(0.0, 2.0, 0.0), 1e-5));
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+
+use crate::OrbitCamera;
+
+/// Component holding the transforms of the last two fixed steps of an `OrbitCamera`.
+///
+/// Inserted automatically when `OrbitCameraPlugin` runs with interpolation enabled. The rendered `Transform`
+/// is interpolated between both by the overstep fraction of `Time<Fixed>`.
+#[derive(Debug, Clone, Component)]
+pub struct InterpolatedTransform {
+    pub previous: Transform,
+    pub current: Transform,
+}
+
+impl InterpolatedTransform {
+    pub fn new(transform: Transform) -> Self {
+        Self {
+            previous: transform,
+            current: transform,
+        }
+    }
+
+    /// Interpolates between the previous and current transform, `t` in the range `0.0..=1.0`.
+    pub fn interpolate(&self, t: f32) -> Transform {
+        Transform {
+            translation: self.previous.translation.lerp(self.current.translation, t),
+            rotation: self.previous.rotation.slerp(self.current.rotation, t),
+            scale: self.previous.scale.lerp(self.current.scale, t),
+        }
+    }
+}
+
+pub(crate) fn interpolation_init(
+    mut commands: Commands,
+    camera_q: Query<(Entity, &Transform), Added<OrbitCamera>>,
+) {
+    for (entity, transform) in camera_q.iter() {
+        commands
+            .entity(entity)
+            .try_insert(InterpolatedTransform::new(*transform));
+    }
+}
+
+/// Restores the last fixed-step transform, since `update_transform` derives the rotation from it.
+pub(crate) fn restore_fixed_transform(
+    mut camera_q: Query<(&mut Transform, &InterpolatedTransform), With<OrbitCamera>>,
+) {
+    for (mut transform, interpolated) in camera_q.iter_mut() {
+        *transform = interpolated.current;
+    }
+}
+
+pub(crate) fn store_fixed_transform(
+    mut camera_q: Query<(&Transform, &mut InterpolatedTransform), With<OrbitCamera>>,
+) {
+    for (transform, mut interpolated) in camera_q.iter_mut() {
+        interpolated.previous = interpolated.current;
+        interpolated.current = *transform;
+    }
+}
+
+pub(crate) fn interpolate_transform(
+    time: Res<Time<Fixed>>,
+    mut camera_q: Query<(&mut Transform, &InterpolatedTransform), With<OrbitCamera>>,
+) {
+    let t = time.overstep_fraction();
+    for (mut transform, interpolated) in camera_q.iter_mut() {
+        *transform = interpolated.interpolate(t);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use crate::{
+        test_util::{spawn_camera, FRAME_TIME},
+        OrbitCameraPlugin,
+    };
+
+    #[test]
+    fn rendered_transform_is_interpolated_between_fixed_steps() {
+        let frame = Duration::from_secs_f32(FRAME_TIME);
+        let mut app = App::new();
+        // a fixed step runs every other frame, rendering the frames in between halfway
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(frame))
+            .insert_resource(Time::<Fixed>::from_duration(2 * frame))
+            .add_plugins(OrbitCameraPlugin::new(FixedUpdate).with_interpolation(true));
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        // the first frame has a time step of zero, the fifth runs the second fixed step, so both steps are settled
+        for _ in 0..5 {
+            app.update();
+        }
+        let before = *app.world.get::<Transform>(camera).unwrap();
+        app.world
+            .get_mut::<OrbitCamera>(camera)
+            .unwrap()
+            .orbit(0.4, 0.2, 0.0);
+        app.update();
+        app.update();
+        // the step applying the orbit renders the previous step
+        assert_eq!(*app.world.get::<Transform>(camera).unwrap(), before);
+
+        app.update();
+        let interpolated = app.world.get::<InterpolatedTransform>(camera).unwrap();
+        assert_eq!(interpolated.previous, before);
+        assert_ne!(interpolated.current, before);
+        assert_eq!(
+            *app.world.get::<Transform>(camera).unwrap(),
+            interpolated.interpolate(0.5)
+        );
+    }
+}
@@ -1,10 +1,29 @@
+pub mod auto_rotate;
+pub mod bookmarks;
+pub mod constraints;
 pub mod controls;
+#[cfg(feature = "debug_overlay")]
+pub mod debug;
+#[cfg(feature = "depth_auto_focus")]
+pub mod depth_focus;
+pub mod follow;
+pub mod interpolation;
+pub mod pose;
+pub mod response;
+#[cfg(test)]
+mod test_util;
+pub mod time;
+pub mod transition;
 pub mod util;
 
 use bevy::transform::TransformSystem::TransformPropagate;
-use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+use bevy::{
+    ecs::schedule::ScheduleLabel,
+    prelude::*,
+    render::{camera::CameraProjection, primitives::Aabb},
+};
 use std::{
-    f32::consts::{PI, TAU},
+    f32::consts::{FRAC_PI_2, PI, TAU},
     fmt::Debug,
     ops::RangeInclusive,
 };
@@ -49,11 +68,57 @@ use std::{
 #[derive(Debug, Clone)]
 pub struct OrbitCameraPlugin<T = PostUpdate> {
     label: T,
+    interpolate: bool,
+    up_axis: UpAxis,
+    time_source: Option<time::TimeSource>,
+    order_before_transform_propagate: bool,
 }
 
 impl Default for OrbitCameraPlugin<PostUpdate> {
     fn default() -> Self {
-        Self { label: PostUpdate }
+        Self::new(PostUpdate)
+    }
+}
+
+impl<T> OrbitCameraPlugin<T> {
+    /// Creates the plugin running the camera systems in the schedule `label`.
+    pub fn new(label: T) -> Self {
+        Self {
+            label,
+            interpolate: false,
+            up_axis: UpAxis::Y,
+            time_source: None,
+            order_before_transform_propagate: true,
+        }
+    }
+
+    /// Interpolates the rendered transform between fixed steps.
+    /// Only meaningful when the plugin runs in `FixedUpdate` or another fixed schedule.
+    pub fn with_interpolation(mut self, interpolate: bool) -> Self {
+        self.interpolate = interpolate;
+        self
+    }
+
+    /// Sets the clock of the time-based camera systems, see [`time::TimeSource`].
+    pub fn with_time_source(mut self, time_source: time::TimeSource) -> Self {
+        self.time_source = Some(time_source);
+        self
+    }
+
+    /// Orders the camera systems before bevy's `TransformPropagate`, which is the default.
+    ///
+    /// Disable it for pipelines without the standard transform propagation, or with a custom one.
+    /// The camera `GlobalTransform` is then only up to date if the propagation is ordered after
+    /// `OrbitCameraSystemSet` by the app, otherwise rendering lags a frame behind the camera.
+    pub fn with_order_before_transform_propagate(mut self, order: bool) -> Self {
+        self.order_before_transform_propagate = order;
+        self
+    }
+
+    /// Sets the up axis of cameras that don't override it with `OrbitCamera::up_axis`.
+    pub fn with_up_axis(mut self, up_axis: UpAxis) -> Self {
+        self.up_axis = up_axis;
+        self
     }
 }
 
@@ -63,15 +128,138 @@ pub struct OrbitCameraSystemSet;
 
 impl<T: ScheduleLabel + Clone> Plugin for OrbitCameraPlugin<T> {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            self.label.clone(),
-            update_transform
-                .in_set(OrbitCameraSystemSet)
-                .before(TransformPropagate),
-        );
+        match self.time_source {
+            Some(time_source) => app.insert_resource(time_source),
+            None => app.init_resource::<time::TimeSource>(),
+        };
+        app.insert_resource(DefaultUpAxis(self.up_axis))
+            .add_event::<transition::ZoomToFrame>()
+            .add_event::<transition::FlyTo>()
+            .add_systems(
+                self.label.clone(),
+                (
+                    up_axis_init,
+                    auto_rotate::auto_rotate,
+                    follow::follow_target,
+                    follow::follow_centroid,
+                    follow::follow_path,
+                    follow::match_target_roll,
+                    follow::speed_fov,
+                    transition::zoom_to_frame,
+                    transition::fly_to,
+                    transition::update_transition,
+                    follow::keep_in_view,
+                    constraints::focus_sphere,
+                    constraints::min_screen_fraction,
+                    update_transform,
+                    constraints::ground_clamp,
+                    follow::update_headlight,
+                    follow::update_reflection,
+                )
+                    .chain()
+                    .in_set(OrbitCameraSystemSet),
+            );
+        if self.order_before_transform_propagate {
+            app.configure_sets(
+                self.label.clone(),
+                OrbitCameraSystemSet.before(TransformPropagate),
+            );
+        }
+        if self.interpolate {
+            app.add_systems(
+                self.label.clone(),
+                (
+                    interpolation::interpolation_init.after(up_axis_init),
+                    interpolation::restore_fixed_transform.before(OrbitCameraSystemSet),
+                    interpolation::store_fixed_transform.after(OrbitCameraSystemSet),
+                ),
+            );
+            if self.order_before_transform_propagate {
+                app.add_systems(
+                    PostUpdate,
+                    interpolation::interpolate_transform.before(TransformPropagate),
+                );
+            } else {
+                app.add_systems(PostUpdate, interpolation::interpolate_transform);
+            }
+        }
     }
 }
 
+/// The vertical axis of the world, used when `OrbitCamera::lock_up_axis` is set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UpAxis {
+    #[default]
+    Y,
+    Z,
+}
+
+impl UpAxis {
+    pub fn vector(self) -> Vec3 {
+        match self {
+            UpAxis::Y => Vec3::Y,
+            UpAxis::Z => Vec3::Z,
+        }
+    }
+
+    /// The rotation from the Y-up frame into this frame.
+    pub fn basis(self) -> Quat {
+        match self {
+            UpAxis::Y => Quat::IDENTITY,
+            UpAxis::Z => Quat::from_rotation_x(FRAC_PI_2),
+        }
+    }
+
+    /// The coordinates of a point in the horizontal plane, XZ for `Y` and XY for `Z`.
+    pub fn horizontal(self, point: Vec3) -> Vec2 {
+        match self {
+            UpAxis::Y => Vec2::new(point.x, point.z),
+            UpAxis::Z => Vec2::new(point.x, point.y),
+        }
+    }
+}
+
+impl From<UpAxis> for Vec3 {
+    fn from(up_axis: UpAxis) -> Self {
+        up_axis.vector()
+    }
+}
+
+/// The six axis-aligned views of a view cube, named after the side of the focus the camera is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ViewDirection {
+    Front,
+    Back,
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl ViewDirection {
+    /// The camera rotation of the view, with `Front` looking along the negative Z axis of the up axis frame.
+    /// The top and bottom views have the front side at the bottom of the screen.
+    pub fn rotation(self, up_axis: UpAxis) -> Quat {
+        let rotation = match self {
+            ViewDirection::Front => Quat::IDENTITY,
+            ViewDirection::Back => Quat::from_rotation_y(PI),
+            ViewDirection::Top => Quat::from_rotation_x(-FRAC_PI_2),
+            ViewDirection::Bottom => Quat::from_rotation_x(FRAC_PI_2),
+            ViewDirection::Left => Quat::from_rotation_y(-FRAC_PI_2),
+            ViewDirection::Right => Quat::from_rotation_y(FRAC_PI_2),
+        };
+        up_axis.basis() * rotation
+    }
+}
+
+/// Resource holding the up axis of cameras that don't override it, set by `OrbitCameraPlugin::with_up_axis`.
+#[derive(Debug, Clone, Copy, Default, Resource)]
+pub struct DefaultUpAxis(pub UpAxis);
+
+/// The fraction of the smaller viewport dimension covered by the scene framed with [`OrbitCamera::fit_scene`].
+const SCENE_SCREEN_FRACTION: f32 = 0.8;
+
 /// Component for Pan-Orbit camera functionality, allowing the camera to orbit around a focus point, zoom in and out, and pan across the scene.
 #[derive(Debug, Clone, Component, PartialEq)]
 pub struct OrbitCamera {
@@ -84,11 +272,147 @@ pub struct OrbitCamera {
     pub delta_pitch: f32,
     pub delta_roll: f32,
     pub pan: Vec2,
+    /// Optional rectangle the focus is kept in, in the plane of [`OrbitCamera::pan_rect_frame`],
+    /// e.g. for document viewers. Only the position within the plane is constrained, moving along the plane normal
+    /// is unaffected.
+    pub pan_rect: Option<Rect>,
 
     /// Optional limit for the camera's radius.
     pub radius_limit: RangeInclusive<Option<f32>>,
+    /// Optional limit for the camera's radius relative to [`OrbitCamera::zoom_baseline`],
+    /// e.g. `10.0` allows zooming 10x in and 10x out.
+    pub relative_zoom_limit: Option<f32>,
+    /// Keeps the radius of perspective cameras at least at the near plane distance,
+    /// so zooming in doesn't clip the focus, see [`OrbitCamera::min_radius_for_near_plane`].
+    pub near_plane_guard: bool,
+    /// Passes through the focus when zooming in beyond the lower radius limit, e.g. for flythrough navigation.
+    /// Instead of stopping at the limit, the camera continues to the opposite side of the focus and turns around
+    /// to look back at it. Further zooming in keeps moving it away from the focus on that side, until zooming
+    /// out brings it back through the focus.
+    ///
+    /// The radius can't cross zero, since zooming scales it, so the pass happens at the lower limit instead.
+    /// It needs a lower bound from `radius_limit` or `relative_zoom_limit` to take effect.
+    pub zoom_through_focus: bool,
 
     pub lock_up_axis: bool,
+    /// Allows the pitch to go past the poles when `lock_up_axis` is set, e.g. to inspect the underside of objects.
+    pub allow_over_pole: bool,
+    /// Keeps the current roll when `lock_up_axis` is set instead of decaying it to zero.
+    pub preserve_roll_on_lock: bool,
+    /// The fraction in the range `0.0..=1.0` of the roll removed per update when `lock_up_axis` is not set
+    /// and the camera isn't being rolled. Small values level out a horizon tilted by drift over long sessions.
+    pub auto_level: f32,
+    /// Orbits about the screen axes the camera would have without roll when `lock_up_axis` is not set,
+    /// so dragging up always tilts the view toward the sky, even when the camera is rolled.
+    pub orbit_unrolled: bool,
+    /// Flips the yaw direction when `lock_up_axis` is not set and the camera is upside down,
+    /// so dragging sideways turns the scene the same way about the world up axis.
+    pub auto_flip_yaw_when_inverted: bool,
+    /// Optional height of the camera along the up axis, e.g. for a drone view circling at a constant altitude.
+    /// The pitch follows from the height, focus and radius, so pitch input has no effect. Zooming in closer than
+    /// the height difference to the focus is limited, since the height couldn't be reached otherwise.
+    pub fixed_camera_height: Option<f32>,
+    /// Optional minimum angle in radians of the camera above the horizontal plane through the focus,
+    /// e.g. `0.0` never lets the camera drop below the focus. Applies in both lock modes.
+    pub min_elevation: Option<f32>,
+    /// Optional angle in radians before the poles within which pitching toward them slows down when
+    /// `lock_up_axis` is set, instead of stopping abruptly at the pole.
+    pub pole_easing_margin: Option<f32>,
+    /// Optional soft limit of the pitch when `lock_up_axis` is set, pushing past it is resisted and springs back.
+    pub pitch_soft_limit: Option<PitchSoftLimit>,
+    /// Optional world point to rotate about instead of the focus, e.g. the point under the cursor at drag start.
+    /// The point stays fixed on screen while rotating; set it when a drag starts and clear it on release.
+    pub rotation_pivot: Option<Vec3>,
+    /// The up axis of this camera. `None` uses the plugin default, which is filled in when the camera is added.
+    pub up_axis: Option<UpAxis>,
+    /// Optional [`constraints::FocusValidator`] correcting every change of the focus in `update_transform`,
+    /// e.g. to keep it over walkable ground.
+    pub focus_validator: Option<constraints::FocusValidator>,
+    /// State kept between updates, read through the accessors of the camera.
+    pub state: OrbitCameraState,
+}
+
+/// The state an [`OrbitCamera`] keeps between updates.
+///
+/// Only readable through the accessors of the camera, e.g. [`OrbitCamera::zoom_baseline`].
+/// Replacing it with the default drops the state, as if the camera was newly spawned.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrbitCameraState {
+    pub(crate) zoom_baseline: Option<f32>,
+    pub(crate) target_radius: Option<f32>,
+    pub(crate) pan_rect_frame: Option<(Vec3, Quat)>,
+    pub(crate) target_orientation: Option<Quat>,
+    pub(crate) pending_flip: bool,
+    pub(crate) zoomed_through: bool,
+    pub(crate) valid_focus: Option<Vec3>,
+    pub(crate) focus_validated_by: Option<constraints::FocusValidator>,
+}
+
+/// Optional component holding the absolute orbit angles of the camera.
+///
+/// When present on an `OrbitCamera` entity, it is kept in sync by `update_transform` after the deltas are applied.
+/// Angles are in radians and follow the `EulerRot::YXZ` decomposition of the camera rotation in the Y-up frame,
+/// so the yaw is about the camera's up axis.
+#[derive(Debug, Clone, Copy, Component, Default, PartialEq)]
+pub struct OrbitAngles {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub roll: f32,
+}
+
+impl OrbitAngles {
+    pub fn from_rotation(rotation: Quat) -> Self {
+        let (yaw, pitch, roll) = rotation.to_euler(EulerRot::YXZ);
+        Self { yaw, pitch, roll }
+    }
+}
+
+/// A pitch band with rubber band edges, see `OrbitCamera::pitch_soft_limit`.
+///
+/// Pitch is in radians, negative values look down.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PitchSoftLimit {
+    pub min: f32,
+    pub max: f32,
+    /// The fraction in the range `0.0..=1.0` of the overshoot removed per update,
+    /// and by which input pushing further out is damped.
+    pub stiffness: f32,
+}
+
+impl PitchSoftLimit {
+    pub fn new(min: f32, max: f32, stiffness: f32) -> Self {
+        Self {
+            min,
+            max,
+            stiffness,
+        }
+    }
+
+    /// Applies a pitch delta, damping it past the limits and springing back toward them.
+    pub fn apply(&self, pitch: f32, mut delta: f32) -> f32 {
+        let resistance = 1.0 - self.stiffness.clamp(0.0, 1.0);
+        if (pitch > self.max && delta > 0.0) || (pitch < self.min && delta < 0.0) {
+            delta *= resistance;
+        }
+        let pitch = pitch + delta;
+        if pitch > self.max {
+            self.max + (pitch - self.max) * resistance
+        } else if pitch < self.min {
+            self.min + (pitch - self.min) * resistance
+        } else {
+            pitch
+        }
+    }
+}
+
+/// Component that stops `update_transform` from writing the `Transform` of a camera, e.g. for a paused backdrop.
+///
+/// Direct changes to the `OrbitCamera` like `focus` and `radius` are kept and show up once the component is removed.
+/// A running `CameraTransition` is paused while frozen, and poses recalled instantly, e.g. from bookmarks, are ignored.
+#[derive(Debug, Clone, Copy, Component, Default)]
+pub struct FreezeTransform {
+    /// Keeps the orbit and pan deltas while frozen so they are applied on unfreeze, otherwise they are dropped.
+    pub accumulate_deltas: bool,
 }
 
 impl Default for OrbitCamera {
@@ -106,8 +430,25 @@ impl OrbitCamera {
             delta_pitch: 0.0,
             delta_roll: 0.0,
             pan: Vec2::ZERO,
+            pan_rect: None,
             radius_limit: RangeInclusive::new(None, None),
+            relative_zoom_limit: None,
+            near_plane_guard: false,
+            zoom_through_focus: false,
             lock_up_axis: false,
+            allow_over_pole: false,
+            preserve_roll_on_lock: false,
+            auto_level: 0.0,
+            orbit_unrolled: false,
+            auto_flip_yaw_when_inverted: false,
+            fixed_camera_height: None,
+            min_elevation: None,
+            pole_easing_margin: None,
+            pitch_soft_limit: None,
+            rotation_pivot: None,
+            up_axis: None,
+            focus_validator: None,
+            state: OrbitCameraState::default(),
         }
     }
 
@@ -134,36 +475,267 @@ impl OrbitCamera {
     }
 
     fn update_transform(&mut self, transform: &mut Transform, projection: &mut Projection) {
+        // snap to a target radius that wasn't picked up by the zoom smoothing of the controls
+        if let Some(target_radius) = self.state.target_radius.take() {
+            self.radius = self.clamp_radius(target_radius);
+        }
+        if self.near_plane_guard {
+            self.radius = self.radius.max(self.min_radius_for_near_plane(projection));
+        }
         let radius = if let Projection::Orthographic(ref mut p) = projection {
+            // the radius is used as scale, which collapses or inverts the projection at zero and below,
+            // whichever way it was set
+            self.radius = self.radius.max(f32::EPSILON);
             p.scale = self.radius;
             (p.far + p.near) / 2.0
         } else {
             self.radius
         };
+        if self.pan_rect.is_some() && self.state.pan_rect_frame.is_none() {
+            self.state.pan_rect_frame = Some((self.focus, transform.rotation));
+        }
         self.focus += transform.rotation * self.pan.extend(0.0);
-        if self.lock_up_axis {
-            let (mut yaw, mut pitch, roll) = transform.rotation.to_euler(EulerRot::YXZ);
-            pitch = (pitch - self.delta_pitch).clamp(-PI / 2.0, PI / 2.0);
+        self.focus = self.clamp_to_pan_rect(self.focus);
+        let previous_rotation = transform.rotation;
+        if let Some(rotation) = self.state.target_orientation.take() {
+            transform.rotation = rotation.normalize();
+        }
+        if std::mem::take(&mut self.state.pending_flip) {
+            // half a turn about the view up axis puts the camera behind the focus, looking back at it
+            transform.rotate_axis(transform.up().into(), PI);
+        }
+        let up_axis = self.up_axis.unwrap_or_default();
+        if self.lock_up_axis && self.allow_over_pole {
+            // rotating about the world up axis and the local horizontal axis keeps the roll at zero
+            // without clamping, and avoids the yaw snap of the euler decomposition at the poles
+            transform.rotate_axis(transform.local_x().into(), -self.delta_pitch);
+            transform.rotate_axis(up_axis.vector(), self.delta_yaw);
+        } else if self.lock_up_axis {
+            // decompose in the Y-up frame so yaw is always about the up axis
+            let rotation = up_axis.basis().inverse() * transform.rotation;
+            let (mut yaw, mut pitch, roll) = rotation.to_euler(EulerRot::YXZ);
+            let mut delta_pitch = -self.delta_pitch;
+            if let Some(margin) = self.pole_easing_margin.filter(|margin| *margin > 0.0) {
+                // slow down proportionally to the remaining distance, so the pole is approached ease-out
+                let remaining = PI / 2.0 - delta_pitch.signum() * pitch;
+                delta_pitch *= (remaining / margin).clamp(0.0, 1.0);
+            }
+            pitch = match self.pitch_soft_limit {
+                Some(limit) => limit.apply(pitch, delta_pitch),
+                None => pitch + delta_pitch,
+            }
+            .clamp(-PI / 2.0, PI / 2.0);
             yaw += self.delta_yaw;
-            let smoothness = 0.6;
+            let roll = if self.preserve_roll_on_lock {
+                roll
+            } else {
+                let smoothness = 0.6;
+                smoothness * (roll % TAU)
+            };
             transform.rotation =
-                Quat::from_euler(EulerRot::YXZ, yaw, pitch, smoothness * (roll % TAU));
+                up_axis.basis() * Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll);
         } else {
-            transform.rotate_axis(transform.local_x().into(), -self.delta_pitch);
-            transform.rotate_axis(transform.local_y().into(), self.delta_yaw);
-            transform.rotate_axis(transform.local_z().into(), self.delta_roll);
+            // yaw, pitch and roll about the local axes in the same order as the euler angles of the locked mode,
+            // so from a level camera the same deltas give the same rotation in both modes
+            let inverted = transform.up().dot(up_axis.vector()) < 0.0;
+            let delta_yaw = if self.auto_flip_yaw_when_inverted && inverted {
+                -self.delta_yaw
+            } else {
+                self.delta_yaw
+            };
+            if self.orbit_unrolled {
+                // the screen axes the camera would have without roll
+                let forward: Vec3 = transform.forward().into();
+                let right = forward
+                    .cross(up_axis.vector())
+                    .try_normalize()
+                    .unwrap_or(transform.local_x().into());
+                let up = right.cross(forward);
+                transform.rotate_axis(right, -self.delta_pitch);
+                transform.rotate_axis(up, delta_yaw);
+                transform.rotate_local_z(self.delta_roll);
+            } else {
+                transform.rotation = (transform.rotation
+                    * Quat::from_euler(
+                        EulerRot::YXZ,
+                        delta_yaw,
+                        -self.delta_pitch,
+                        self.delta_roll,
+                    ))
+                .normalize();
+            }
+            if self.auto_level > 0.0 && self.delta_roll == 0.0 {
+                let rotation = up_axis.basis().inverse() * transform.rotation;
+                let (yaw, pitch, roll) = rotation.to_euler(EulerRot::YXZ);
+                // the roll is ill-defined when looking straight up or down
+                if pitch.cos() > 0.1 {
+                    let roll = roll * (1.0 - self.auto_level.clamp(0.0, 1.0));
+                    transform.rotation =
+                        up_axis.basis() * Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll);
+                }
+            }
+        }
+        if let Some(pivot) = self.rotation_pivot {
+            // orbit the pivot instead of the focus, which keeps the pivot at the same screen position
+            let rotation = transform.rotation * previous_rotation.inverse();
+            self.focus = pivot + rotation * (self.focus - pivot);
+        }
+        if let Some(validator) = &self.focus_validator {
+            // validated here rather than in the system, so previews like `pending_pose` and focus changes
+            // made before the update, e.g. by following a target, agree with what is rendered
+            if self.state.focus_validated_by.as_ref() != Some(validator) {
+                // a new validator checks the current focus again
+                self.state.focus_validated_by = Some(validator.clone());
+                self.state.valid_focus = None;
+            }
+            if self.state.valid_focus != Some(self.focus) {
+                let previous = self.state.valid_focus.unwrap_or(self.focus);
+                self.focus = validator.validate(previous, self.focus);
+                self.state.valid_focus = Some(self.focus);
+            }
         }
         self.reset_rotation_and_pan_deltas();
+        let mut radius = radius;
+        if let Some(height) = self.fixed_camera_height {
+            let up = up_axis.vector();
+            let offset = height - self.focus.dot(up);
+            if !matches!(projection, Projection::Orthographic(_)) && offset.abs() > radius {
+                // the height can't be reached from closer than the height difference
+                self.radius = offset.abs();
+                radius = self.radius;
+            }
+            if radius > 0.0 {
+                let elevation = (offset / radius).clamp(-1.0, 1.0).asin();
+                transform.rotation = constraints::with_elevation(transform.rotation, up, elevation);
+            }
+        }
         transform.translation = self.focus + transform.rotation * Vec3::new(0.0, 0.0, radius);
+        if let Some(min_elevation) = self.min_elevation {
+            constraints::clamp_elevation(transform, self.focus, up_axis.vector(), min_elevation);
+        }
+    }
+
+    /// The orientation of the camera after the next update, without the pending deltas.
+    pub fn orientation(&self, transform: &Transform) -> Quat {
+        self.state.target_orientation.unwrap_or(transform.rotation)
+    }
+
+    /// Sets the orientation of the camera on the next update, replacing pending rotation deltas.
+    /// The camera keeps its focus and radius and moves around the focus to look along the new orientation.
+    ///
+    /// With `lock_up_axis`, any roll of `rotation` is removed like for the deltas, unless `preserve_roll_on_lock` is set.
+    pub fn set_orientation(&mut self, rotation: Quat) {
+        self.delta_yaw = 0.0;
+        self.delta_pitch = 0.0;
+        self.delta_roll = 0.0;
+        self.state.target_orientation = Some(rotation);
+    }
+
+    /// The orientation set with [`OrbitCamera::set_orientation`] that hasn't been applied yet.
+    pub fn target_orientation(&self) -> Option<Quat> {
+        self.state.target_orientation
+    }
+
+    /// Orients the camera to look at the focus from one of the axis-aligned views on the next update.
+    ///
+    /// To animate the change, insert a `CameraTransition` to a pose with [`ViewDirection::rotation`] instead.
+    pub fn view_from(&mut self, direction: ViewDirection) {
+        self.set_orientation(direction.rotation(self.up_axis.unwrap_or_default()));
+    }
+
+    /// Sets the radius through the zoom smoothing of `OrbitControlsPlugin`, so the change eases in.
+    /// The smoothing picks the radius up on its next run, so it doesn't matter whether this is called before
+    /// or after `OrbitControlsSystemSet`. Without the controls, or with zooming disabled, the radius is set
+    /// directly on the next update.
+    pub fn set_radius_smoothed(&mut self, radius: f32) {
+        self.state.target_radius = Some(radius);
+    }
+
+    /// The radius set with [`OrbitCamera::set_radius_smoothed`] that hasn't been picked up yet.
+    pub fn target_radius(&self) -> Option<f32> {
+        self.state.target_radius
+    }
+
+    /// Sets the radius without clamping it to `radius_limit` and `relative_zoom_limit`, e.g. for framing an object
+    /// that doesn't fit within the limits. This deliberately bypasses the limits, which apply again on the next zoom.
+    pub fn set_radius_unclamped(&mut self, radius: f32) {
+        self.state.target_radius = None;
+        self.radius = radius;
     }
 
     pub fn zoom(&mut self, factor: f32) {
-        self.radius *= factor;
+        if self.relative_zoom_limit.is_some() && self.state.zoom_baseline.is_none() {
+            self.state.zoom_baseline = Some(self.radius);
+        }
+        let factor = if self.zoom_through_focus && self.state.zoomed_through {
+            // the camera looks back at the focus, so zooming in moves it on away from the focus
+            1.0 / factor.max(f32::EPSILON)
+        } else {
+            factor
+        };
+        let radius = self.radius * factor;
+        let clamped = self.clamp_radius(radius);
+        if self.zoom_through_focus && radius < clamped {
+            // continue past the limit on the opposite side, the overshoot mirrored at the limit
+            self.state.pending_flip = !self.state.pending_flip;
+            self.state.zoomed_through = !self.state.zoomed_through;
+            self.radius = self.clamp_radius(2.0 * clamped - radius);
+        } else {
+            self.radius = clamped;
+        }
+    }
+
+    /// Whether the camera turns around to the opposite side of the focus on the next update, after
+    /// [`OrbitCamera::zoom`] zoomed through the focus.
+    pub fn pending_flip(&self) -> bool {
+        self.state.pending_flip
+    }
+
+    /// Whether the camera has passed through the focus with `zoom_through_focus`, which inverts the zoom
+    /// so it keeps moving in the direction it passed through.
+    pub fn zoomed_through(&self) -> bool {
+        self.state.zoomed_through
+    }
+
+    /// The last focus accepted by `focus_validator`, which a rejected change falls back to.
+    pub fn valid_focus(&self) -> Option<Vec3> {
+        self.state.valid_focus
+    }
+
+    /// The radius `relative_zoom_limit` is relative to, captured on the first zoom.
+    pub fn zoom_baseline(&self) -> Option<f32> {
+        self.state.zoom_baseline
+    }
+
+    /// The origin and orientation of the `pan_rect` plane, the rect's X and Y axes are the local X and Y axes
+    /// of the rotation. Captured from the focus and view on the first update with a `pan_rect`.
+    pub fn pan_rect_frame(&self) -> Option<(Vec3, Quat)> {
+        self.state.pan_rect_frame
+    }
+
+    /// Clamps a radius to the `radius_limit` and `relative_zoom_limit`.
+    pub fn clamp_radius(&self, mut radius: f32) -> f32 {
+        if let (Some(limit), Some(baseline)) = (self.relative_zoom_limit, self.state.zoom_baseline)
+        {
+            radius = radius.clamp(baseline / limit.max(1.0), baseline * limit.max(1.0));
+        }
         if let Some(lower) = self.radius_limit.start() {
-            self.radius = self.radius.max(*lower);
+            radius = radius.max(*lower);
         }
         if let Some(upper) = self.radius_limit.end() {
-            self.radius = self.radius.min(*upper);
+            radius = radius.min(*upper);
+        }
+        radius
+    }
+
+    /// The smallest radius at which the focus isn't clipped by the near plane.
+    ///
+    /// Orthographic cameras are placed at a fixed distance and use the radius as scale, which can't clip the focus,
+    /// so the result is `0.0` for them.
+    pub fn min_radius_for_near_plane(&self, projection: &Projection) -> f32 {
+        match projection {
+            Projection::Perspective(p) => p.near,
+            Projection::Orthographic(_) => 0.0,
         }
     }
 
@@ -171,6 +743,50 @@ impl OrbitCamera {
         self.pan += delta;
     }
 
+    /// `focus` moved into the `pan_rect`, or unchanged without one or before its frame is captured.
+    pub(crate) fn clamp_to_pan_rect(&self, focus: Vec3) -> Vec3 {
+        let (Some(rect), Some((origin, rotation))) = (self.pan_rect, self.state.pan_rect_frame)
+        else {
+            return focus;
+        };
+        let local = rotation.inverse() * (focus - origin);
+        let clamped = local.truncate().clamp(rect.min, rect.max);
+        origin + rotation * clamped.extend(local.z)
+    }
+
+    /// The view-projection matrix of the camera once its pending deltas have been applied by `update_transform`,
+    /// without waiting for transform propagation. `viewport_size` is the logical size of the viewport.
+    pub fn view_projection(
+        &self,
+        transform: &Transform,
+        projection: &Projection,
+        viewport_size: Vec2,
+    ) -> Mat4 {
+        let mut property = self.clone();
+        let mut transform = *transform;
+        let mut projection = projection.clone();
+        property.update_transform(&mut transform, &mut projection);
+        projection.update(viewport_size.x, viewport_size.y);
+        projection.get_projection_matrix() * transform.compute_matrix().inverse()
+    }
+
+    /// Pans by whole viewports, e.g. `Vec2::X` moves the focus right by one viewport width on the focus plane.
+    /// Does nothing if the viewport size is unknown.
+    pub fn pan_screens(&mut self, screens: Vec2, camera: &Camera, projection: &Projection) {
+        let Some(viewport_size) = camera.physical_viewport_size() else {
+            return;
+        };
+        if let Some(factor) = util::calculate_pan_scaling_factor(camera, projection, self) {
+            self.pan(screens * viewport_size.as_vec2() * factor);
+        }
+    }
+
+    /// Moves the focus along the view direction of `transform`, the camera follows at the same radius.
+    pub fn dolly_forward(&mut self, transform: &Transform, distance: f32) {
+        let forward: Vec3 = transform.forward().into();
+        self.focus += distance * forward;
+    }
+
     pub fn orbit(&mut self, delta_yaw: f32, delta_pitch: f32, delta_roll: f32) {
         self.delta_yaw += delta_yaw;
         self.delta_pitch += delta_pitch;
@@ -188,10 +804,775 @@ impl OrbitCamera {
     pub fn roll(&mut self, delta: f32) {
         self.delta_roll += delta;
     }
+
+    /// The radius at which a sphere of `object_radius` around the focus covers `fraction` of the smaller
+    /// viewport dimension. For orthographic projections the result is the scale.
+    ///
+    /// `viewport_size` is the logical size of the viewport.
+    pub fn distance_for_screen_fraction(
+        object_radius: f32,
+        fraction: f32,
+        projection: &Projection,
+        viewport_size: Vec2,
+    ) -> f32 {
+        match projection {
+            Projection::Perspective(p) => {
+                // tangent of the half angle spanned by the smaller viewport dimension
+                let tan_half_fov =
+                    (0.5 * p.fov).tan() * viewport_size.min_element() / viewport_size.y;
+                object_radius / (fraction * tan_half_fov).atan().sin()
+            }
+            Projection::Orthographic(p) => {
+                let area = util::calculate_orthographic_area(p, 1.0, viewport_size);
+                2.0 * object_radius / (fraction * area.min_element())
+            }
+        }
+    }
+
+    /// A focus and radius for a pleasant initial view of a scene, e.g. a loaded glTF, from its combined bounds.
+    ///
+    /// The focus is the center of the bounds and the bounding sphere covers most of the smaller viewport dimension,
+    /// leaving a margin. `aspect_ratio` is the viewport width divided by its height; for orthographic projections
+    /// scaling with the window size, the result assumes a viewport height of one.
+    pub fn fit_scene(aabb: &Aabb, projection: &Projection, aspect_ratio: f32) -> (Vec3, f32) {
+        let focus = Vec3::from(aabb.center);
+        let object_radius = aabb.half_extents.length();
+        let viewport_size = Vec2::new(aspect_ratio, 1.0);
+        let radius = Self::distance_for_screen_fraction(
+            object_radius,
+            SCENE_SCREEN_FRACTION,
+            projection,
+            viewport_size,
+        );
+        (focus, radius)
+    }
+
+    /// The angle in radians subtended by a sphere of `object_radius` around the focus.
+    pub fn apparent_angular_size(&self, object_radius: f32) -> f32 {
+        if object_radius >= self.radius {
+            return PI;
+        }
+        2.0 * (object_radius / self.radius).asin()
+    }
+
+    /// The diameter in logical pixels of a sphere of `object_radius` around the focus as it appears on screen.
+    /// Returns `None` if the viewport size is unknown.
+    pub fn apparent_screen_size(
+        &self,
+        object_radius: f32,
+        camera: &Camera,
+        projection: &Projection,
+    ) -> Option<f32> {
+        let viewport_size = camera.logical_viewport_size()?;
+        let size = match projection {
+            Projection::Perspective(p) => {
+                let half_angle = 0.5 * self.apparent_angular_size(object_radius);
+                viewport_size.y * half_angle.tan() / (0.5 * p.fov).tan()
+            }
+            Projection::Orthographic(p) => {
+                let area = util::calculate_orthographic_area(p, self.radius, viewport_size);
+                viewport_size.y * 2.0 * object_radius / area.y
+            }
+        };
+        Some(size)
+    }
 }
 
-fn update_transform(mut query: Query<(&mut OrbitCamera, &mut Transform, &mut Projection)>) {
-    for (mut pan_orbit_camera, mut transform, mut projection) in query.iter_mut() {
+type UpdateTransformQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut OrbitCamera,
+        &'static mut Transform,
+        &'static mut Projection,
+        Option<&'static mut OrbitAngles>,
+        Option<&'static FreezeTransform>,
+        Has<controls::TargetZoom>,
+    ),
+>;
+
+fn update_transform(
+    mut query: UpdateTransformQuery,
+    controls_config: Option<Res<controls::OrbitControlsConfig>>,
+) {
+    // the zoom smoothing of the controls picks up a target radius on its next run, whichever order it was set in
+    let controls_zoom = controls_config.is_some_and(|config| config.enable && config.enable_zoom);
+    for (mut pan_orbit_camera, mut transform, mut projection, angles, freeze, has_target_zoom) in
+        query.iter_mut()
+    {
+        if let Some(freeze) = freeze {
+            if !freeze.accumulate_deltas {
+                pan_orbit_camera.reset_rotation_and_pan_deltas();
+            }
+            continue;
+        }
+        let target_radius = if controls_zoom && has_target_zoom {
+            pan_orbit_camera.state.target_radius.take()
+        } else {
+            None
+        };
         pan_orbit_camera.update_transform(&mut transform, &mut projection);
+        if target_radius.is_some() {
+            pan_orbit_camera.state.target_radius = target_radius;
+        }
+        if let Some(mut angles) = angles {
+            let up_axis = pan_orbit_camera.up_axis.unwrap_or_default();
+            *angles = OrbitAngles::from_rotation(up_axis.basis().inverse() * transform.rotation);
+        }
+    }
+}
+
+/// Applies the default up axis to new cameras and turns an identity orientation into the up axis frame,
+/// so a Z-up camera with a default transform looks horizontally like a Y-up one.
+///
+/// Any other orientation is taken as authored in world space, e.g. with `Transform::looking_at`, and kept.
+fn up_axis_init(
+    default_up_axis: Res<DefaultUpAxis>,
+    mut query: Query<(&mut OrbitCamera, &mut Transform), Added<OrbitCamera>>,
+) {
+    for (mut pan_orbit_camera, mut transform) in query.iter_mut() {
+        let up_axis = *pan_orbit_camera.up_axis.get_or_insert(default_up_axis.0);
+        if transform.rotation.abs_diff_eq(Quat::IDENTITY, 1e-6) {
+            transform.rotation = up_axis.basis();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::camera::ScalingMode;
+
+    use crate::test_util::{spawn_camera, test_app, test_camera};
+
+    #[test]
+    fn orbit_angles_follow_the_orbit() {
+        let mut app = test_app();
+        let orbit = OrbitCamera {
+            lock_up_axis: true,
+            ..default()
+        };
+        let camera = spawn_camera(&mut app, orbit);
+        app.world.entity_mut(camera).insert(OrbitAngles::default());
+        app.update();
+        let mut orbit = app.world.get_mut::<OrbitCamera>(camera).unwrap();
+        orbit.orbit(0.5, 0.25, 0.0);
+        app.update();
+        let angles = app.world.get::<OrbitAngles>(camera).unwrap();
+        assert!((angles.yaw - 0.5).abs() < 1e-5);
+        // a positive pitch delta tilts the camera down
+        assert!((angles.pitch + 0.25).abs() < 1e-5);
+        assert!(angles.roll.abs() < 1e-5);
+    }
+
+    #[test]
+    fn orbiting_over_the_pole_is_continuous() {
+        let mut orbit = OrbitCamera {
+            lock_up_axis: true,
+            allow_over_pole: true,
+            ..OrbitCamera::new(Vec3::ZERO, 5.0)
+        };
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        orbit.update_transform(&mut transform, &mut projection);
+        let mut forward: Vec3 = transform.forward().into();
+
+        // 40 steps of 0.1 pass the pole at a pitch of PI / 2
+        for _ in 0..40 {
+            orbit.pitch(0.1);
+            orbit.update_transform(&mut transform, &mut projection);
+            let next: Vec3 = transform.forward().into();
+            assert!((forward.angle_between(next) - 0.1).abs() < 1e-3);
+            // no yaw snap: the view stays in the vertical plane it started in
+            assert!(next.x.abs() < 1e-4);
+            // and no roll: the right vector stays horizontal
+            assert!(transform.local_x().y.abs() < 1e-4);
+            forward = next;
+        }
+        assert!(forward.z > 0.0);
+    }
+
+    #[test]
+    fn roll_is_preserved_when_locking_the_up_axis() {
+        let roll_after_locking = |preserve_roll_on_lock: bool| {
+            let mut orbit = OrbitCamera::new(Vec3::ZERO, 5.0).with_orbit(0.0, 0.0, 0.3);
+            let mut transform = Transform::default();
+            let mut projection = Projection::default();
+            orbit.update_transform(&mut transform, &mut projection);
+            orbit.lock_up_axis = true;
+            orbit.preserve_roll_on_lock = preserve_roll_on_lock;
+            for _ in 0..10 {
+                orbit.orbit(0.1, 0.05, 0.0);
+                orbit.update_transform(&mut transform, &mut projection);
+            }
+            transform.rotation.to_euler(EulerRot::YXZ).2
+        };
+
+        assert!((roll_after_locking(true) - 0.3).abs() < 1e-4);
+        assert!(roll_after_locking(false).abs() < 0.01);
+    }
+
+    #[test]
+    fn apparent_angular_size_shrinks_with_the_distance() {
+        let near = OrbitCamera::new(Vec3::ZERO, 2.0).apparent_angular_size(1.0);
+        let far = OrbitCamera::new(Vec3::ZERO, 20.0).apparent_angular_size(1.0);
+        assert!((near - PI / 3.0).abs() < 1e-5);
+        assert!((far - 2.0 * 0.05f32.asin()).abs() < 1e-6);
+        // about inversely proportional once the object is small
+        assert!((far * 20.0 - 2.0).abs() < 1e-2);
+        // from inside the sphere it covers the whole view
+        assert_eq!(
+            OrbitCamera::new(Vec3::ZERO, 0.5).apparent_angular_size(1.0),
+            PI
+        );
+    }
+
+    #[test]
+    fn zoom_is_clamped_relative_to_the_first_radius() {
+        let mut orbit = OrbitCamera {
+            relative_zoom_limit: Some(10.0),
+            ..OrbitCamera::new(Vec3::ZERO, 4.0)
+        };
+        for _ in 0..50 {
+            orbit.zoom(0.5);
+        }
+        assert_eq!(orbit.zoom_baseline(), Some(4.0));
+        assert!((orbit.radius - 0.4).abs() < 1e-5);
+        for _ in 0..100 {
+            orbit.zoom(2.0);
+        }
+        assert!((orbit.radius - 40.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn rotation_pivot_stays_under_the_cursor() {
+        let mut orbit = OrbitCamera::new(Vec3::ZERO, 8.0);
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        orbit.update_transform(&mut transform, &mut projection);
+        // a point off the focus, as picked under the cursor at the start of the drag
+        let pivot = Vec3::new(2.0, 1.0, -1.0);
+        orbit.rotation_pivot = Some(pivot);
+        let view_position = transform.compute_matrix().inverse().transform_point3(pivot);
+
+        for _ in 0..5 {
+            orbit.orbit(0.2, 0.1, 0.0);
+            orbit.update_transform(&mut transform, &mut projection);
+            let after = transform.compute_matrix().inverse().transform_point3(pivot);
+            assert!(after.abs_diff_eq(view_position, 1e-4));
+        }
+        assert!(!transform.rotation.abs_diff_eq(Quat::IDENTITY, 1e-3));
+    }
+
+    #[test]
+    fn z_up_camera_mirrors_the_y_up_camera() {
+        for lock_up_axis in [false, true] {
+            let run = |up_axis: UpAxis| {
+                let mut orbit = OrbitCamera {
+                    lock_up_axis,
+                    up_axis: Some(up_axis),
+                    ..OrbitCamera::new(Vec3::ZERO, 5.0)
+                };
+                let mut transform = Transform::from_rotation(up_axis.basis());
+                let mut projection = Projection::default();
+                for _ in 0..3 {
+                    orbit.orbit(0.3, 0.2, 0.0);
+                    orbit.pan(Vec2::new(0.5, -0.25));
+                    orbit.update_transform(&mut transform, &mut projection);
+                }
+                (orbit.focus, transform)
+            };
+
+            let (y_focus, y_transform) = run(UpAxis::Y);
+            let (z_focus, z_transform) = run(UpAxis::Z);
+            let basis = UpAxis::Z.basis();
+            assert!((basis * y_focus).abs_diff_eq(z_focus, 1e-4));
+            assert!((basis * y_transform.translation).abs_diff_eq(z_transform.translation, 1e-4));
+            assert!((basis * y_transform.rotation).abs_diff_eq(z_transform.rotation, 1e-4));
+        }
+    }
+
+    #[test]
+    fn from_direction_uses_the_up_axis() {
+        let y_up = util::from_direction_with_up_axis(Vec3::new(1.0, -1.0, -1.0), UpAxis::Y);
+        let z_up = util::from_direction_with_up_axis(
+            UpAxis::Z.basis() * Vec3::new(1.0, -1.0, -1.0),
+            UpAxis::Z,
+        );
+        // compared as matrices, since the quaternions may differ in sign
+        let expected = Mat3::from_quat(UpAxis::Z.basis() * y_up);
+        assert!(expected.abs_diff_eq(Mat3::from_quat(z_up), 1e-5));
+    }
+
+    #[test]
+    fn dolly_moves_the_focus_and_camera_forward() {
+        let mut orbit = OrbitCamera::new(Vec3::new(1.0, 0.0, 0.0), 5.0).with_orbit(0.7, 0.4, 0.0);
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        orbit.update_transform(&mut transform, &mut projection);
+        let (focus, translation) = (orbit.focus, transform.translation);
+        let forward: Vec3 = transform.forward().into();
+
+        orbit.dolly_forward(&transform, 2.0);
+        orbit.update_transform(&mut transform, &mut projection);
+        assert!(orbit.focus.abs_diff_eq(focus + 2.0 * forward, 1e-5));
+        assert!(transform
+            .translation
+            .abs_diff_eq(translation + 2.0 * forward, 1e-4));
+        assert_eq!(orbit.radius, 5.0);
+    }
+
+    #[test]
+    fn frozen_camera_keeps_its_transform_until_unfrozen() {
+        for accumulate_deltas in [false, true] {
+            let mut app = test_app();
+            let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+            app.update();
+            let before = *app.world.get::<Transform>(camera).unwrap();
+            app.world
+                .entity_mut(camera)
+                .insert(FreezeTransform { accumulate_deltas });
+            app.world
+                .get_mut::<OrbitCamera>(camera)
+                .unwrap()
+                .orbit(0.5, 0.0, 0.0);
+            app.update();
+            app.update();
+            assert_eq!(*app.world.get::<Transform>(camera).unwrap(), before);
+
+            app.world.entity_mut(camera).remove::<FreezeTransform>();
+            app.update();
+            let after = app.world.get::<Transform>(camera).unwrap();
+            let expected = Quat::from_rotation_y(0.5);
+            if accumulate_deltas {
+                assert!(after
+                    .translation
+                    .abs_diff_eq(expected * Vec3::new(0.0, 0.0, 5.0), 1e-4));
+                assert!(after.rotation.abs_diff_eq(expected, 1e-4));
+            } else {
+                assert!(after.translation.abs_diff_eq(before.translation, 1e-5));
+            }
+        }
+    }
+
+    #[test]
+    fn soft_pitch_limit_decelerates_and_springs_back() {
+        let limit = PitchSoftLimit::new(-0.8, -0.2, 0.5);
+        let mut pitch = -0.5;
+        let mut steps = Vec::new();
+        for _ in 0..10 {
+            let next = limit.apply(pitch, -0.1);
+            steps.push(pitch - next);
+            pitch = next;
+        }
+        assert!(pitch < -0.8);
+        // once past the limit the pushes move the pitch less and less, until it stops
+        let past_limit = &steps[3..];
+        assert!(past_limit.windows(2).all(|pair| pair[1] <= pair[0] + 1e-6));
+        assert!(steps[0] > 0.099);
+        assert!(steps[9] < 0.01);
+
+        for _ in 0..30 {
+            pitch = limit.apply(pitch, 0.0);
+        }
+        assert!((pitch + 0.8).abs() < 1e-5);
+    }
+
+    #[test]
+    fn distance_for_screen_fraction_fills_the_fraction_in_both_projections() {
+        let viewport_size = Vec2::new(800.0, 600.0);
+
+        let perspective = PerspectiveProjection::default();
+        let distance = OrbitCamera::distance_for_screen_fraction(
+            1.0,
+            0.5,
+            &Projection::Perspective(perspective.clone()),
+            viewport_size,
+        );
+        // the tangent of the angular radius of the sphere, relative to that of half the viewport height
+        let tan_half_angle = (1.0 / distance).asin().tan();
+        let fraction = tan_half_angle / (0.5 * perspective.fov).tan();
+        assert!((fraction - 0.5).abs() < 1e-5);
+
+        let orthographic = OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical(2.0),
+            ..default()
+        };
+        let scale = OrbitCamera::distance_for_screen_fraction(
+            1.0,
+            0.5,
+            &Projection::Orthographic(orthographic.clone()),
+            viewport_size,
+        );
+        let area = util::calculate_orthographic_area(&orthographic, scale, viewport_size);
+        assert!((2.0 / area.min_element() - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn auto_level_keeps_the_roll_drift_small_in_free_mode() {
+        let roll_after_orbiting = |auto_level: f32| {
+            let mut orbit = OrbitCamera {
+                auto_level,
+                ..OrbitCamera::new(Vec3::ZERO, 5.0).with_orbit(0.0, 0.5, 0.0)
+            };
+            let mut transform = Transform::default();
+            let mut projection = Projection::default();
+            orbit.update_transform(&mut transform, &mut projection);
+            // yawing about the local up axis of a pitched camera tilts its horizon
+            let mut max_roll = 0.0f32;
+            for _ in 0..160 {
+                orbit.yaw(0.01);
+                orbit.update_transform(&mut transform, &mut projection);
+                max_roll = max_roll.max(transform.rotation.to_euler(EulerRot::YXZ).2.abs());
+            }
+            max_roll
+        };
+
+        assert!(roll_after_orbiting(0.0) > 0.1);
+        assert!(roll_after_orbiting(0.5) < 0.01);
+    }
+
+    #[test]
+    fn plugin_updates_without_the_transform_propagate_ordering() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(OrbitCameraPlugin::default().with_order_before_transform_propagate(false));
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::X, 5.0));
+        app.update();
+        let transform = app.world.get::<Transform>(camera).unwrap();
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.0, 0.0, 5.0), 1e-5));
+    }
+
+    #[test]
+    fn unclamped_radius_ignores_the_limits_until_the_next_zoom() {
+        let mut orbit = OrbitCamera {
+            radius_limit: RangeInclusive::new(Some(1.0), Some(5.0)),
+            ..OrbitCamera::new(Vec3::ZERO, 3.0)
+        };
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        // a radius framing an object too large for the limits
+        orbit.set_radius_unclamped(20.0);
+        orbit.update_transform(&mut transform, &mut projection);
+        assert_eq!(orbit.radius, 20.0);
+        assert!((transform.translation.length() - 20.0).abs() < 1e-4);
+
+        orbit.zoom(0.9);
+        assert_eq!(orbit.radius, 5.0);
+    }
+
+    #[test]
+    fn pan_is_clamped_to_the_rect() {
+        let mut orbit = OrbitCamera {
+            pan_rect: Some(Rect::new(-1.0, -2.0, 1.0, 2.0)),
+            ..OrbitCamera::new(Vec3::new(0.0, 0.0, -3.0), 5.0)
+        };
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        orbit.update_transform(&mut transform, &mut projection);
+
+        orbit.pan(Vec2::new(5.0, 0.5));
+        orbit.update_transform(&mut transform, &mut projection);
+        assert!(orbit.focus.abs_diff_eq(Vec3::new(1.0, 0.5, -3.0), 1e-5));
+        // the rect stays aligned to the initial view after orbiting
+        orbit.orbit(0.8, 0.0, 0.0);
+        orbit.update_transform(&mut transform, &mut projection);
+        orbit.pan(Vec2::new(-10.0, -10.0));
+        orbit.update_transform(&mut transform, &mut projection);
+        assert!(orbit.focus.xy().abs_diff_eq(Vec2::new(-1.0, -2.0), 1e-4));
+    }
+
+    #[test]
+    fn near_plane_guard_keeps_the_focus_beyond_the_near_plane() {
+        let mut projection = Projection::Perspective(PerspectiveProjection {
+            near: 0.5,
+            ..default()
+        });
+        let mut orbit = OrbitCamera {
+            near_plane_guard: true,
+            ..OrbitCamera::new(Vec3::ZERO, 5.0)
+        };
+        assert_eq!(orbit.min_radius_for_near_plane(&projection), 0.5);
+        let mut transform = Transform::default();
+        for _ in 0..50 {
+            orbit.zoom(0.5);
+            orbit.update_transform(&mut transform, &mut projection);
+            assert!(orbit.radius >= 0.5);
+            assert!(transform.translation.distance(orbit.focus) >= 0.5 - 1e-5);
+        }
+        let orthographic = Projection::Orthographic(OrthographicProjection::default());
+        assert_eq!(orbit.min_radius_for_near_plane(&orthographic), 0.0);
+    }
+
+    #[test]
+    fn orientation_round_trips_through_the_update() {
+        let mut orbit = OrbitCamera::new(Vec3::new(1.0, 2.0, 3.0), 5.0).with_orbit(0.3, 0.2, 0.0);
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        orbit.update_transform(&mut transform, &mut projection);
+
+        let rotation = Quat::from_euler(EulerRot::YXZ, -1.2, 0.4, 0.3);
+        orbit.set_orientation(rotation);
+        assert_eq!(orbit.orientation(&transform), rotation);
+        orbit.update_transform(&mut transform, &mut projection);
+        assert!(orbit.orientation(&transform).abs_diff_eq(rotation, 1e-6));
+        assert!(transform
+            .translation
+            .abs_diff_eq(orbit.focus + rotation * Vec3::new(0.0, 0.0, 5.0), 1e-4));
+    }
+
+    #[test]
+    fn the_top_view_looks_straight_down() {
+        let mut orbit = OrbitCamera::new(Vec3::new(1.0, 2.0, 3.0), 5.0).with_orbit(0.3, 0.2, 0.0);
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        orbit.update_transform(&mut transform, &mut projection);
+
+        orbit.view_from(ViewDirection::Top);
+        orbit.update_transform(&mut transform, &mut projection);
+        let forward: Vec3 = transform.forward().into();
+        assert!(forward.abs_diff_eq(Vec3::NEG_Y, 1e-5));
+        assert!(transform
+            .translation
+            .abs_diff_eq(orbit.focus + Vec3::new(0.0, 5.0, 0.0), 1e-4));
+        // the front side is at the bottom of the screen
+        let up: Vec3 = transform.up().into();
+        assert!(up.abs_diff_eq(Vec3::NEG_Z, 1e-5));
+    }
+
+    #[test]
+    fn view_directions_follow_the_up_axis() {
+        for (direction, forward) in [
+            (ViewDirection::Front, Vec3::Y),
+            (ViewDirection::Back, Vec3::NEG_Y),
+            (ViewDirection::Top, Vec3::NEG_Z),
+            (ViewDirection::Bottom, Vec3::Z),
+            (ViewDirection::Left, Vec3::X),
+            (ViewDirection::Right, Vec3::NEG_X),
+        ] {
+            let rotation = direction.rotation(UpAxis::Z);
+            assert!((rotation * Vec3::NEG_Z).abs_diff_eq(forward, 1e-5));
+        }
+    }
+
+    #[test]
+    fn fit_scene_frames_a_unit_cube() {
+        let aabb = Aabb::from_min_max(Vec3::ZERO, Vec3::ONE);
+        let projection = Projection::default();
+        let (focus, radius) = OrbitCamera::fit_scene(&aabb, &projection, 16.0 / 9.0);
+        assert!(focus.abs_diff_eq(Vec3::splat(0.5), 1e-6));
+
+        // the bounding sphere fits in the vertical field of view with a margin
+        let Projection::Perspective(p) = &projection else {
+            unreachable!();
+        };
+        let size =
+            OrbitCamera::new(focus, radius).apparent_angular_size(aabb.half_extents.length());
+        assert!(size < p.fov && size > 0.5 * p.fov);
+
+        // a portrait viewport is limited by its width, so the camera backs off
+        let (_, portrait_radius) = OrbitCamera::fit_scene(&aabb, &projection, 0.5);
+        assert!(portrait_radius > radius);
+    }
+
+    #[test]
+    fn free_and_locked_modes_orbit_a_level_camera_alike() {
+        let forward_after_orbit = |lock_up_axis: bool| {
+            let mut orbit = OrbitCamera::new(Vec3::ZERO, 5.0).with_orbit(0.7, 0.0, 0.0);
+            orbit.lock_up_axis = lock_up_axis;
+            let mut transform = Transform::default();
+            let mut projection = Projection::default();
+            orbit.update_transform(&mut transform, &mut projection);
+            orbit.orbit(0.4, 0.3, 0.0);
+            orbit.update_transform(&mut transform, &mut projection);
+            Vec3::from(transform.forward())
+        };
+
+        let free = forward_after_orbit(false);
+        let locked = forward_after_orbit(true);
+        assert!(free.abs_diff_eq(locked, 1e-5));
+        // a positive yaw orbits the camera the same way in both modes
+        assert!(free.x < 0.0 && free.y < 0.0);
+    }
+
+    #[test]
+    fn yaw_keeps_its_world_direction_upside_down() {
+        let forward_after_yaw = |roll: f32, auto_flip: bool| {
+            let mut orbit = OrbitCamera::new(Vec3::ZERO, 5.0).with_orbit(0.0, 0.0, roll);
+            orbit.auto_flip_yaw_when_inverted = auto_flip;
+            let mut transform = Transform::default();
+            let mut projection = Projection::default();
+            orbit.update_transform(&mut transform, &mut projection);
+            orbit.orbit(0.2, 0.0, 0.0);
+            orbit.update_transform(&mut transform, &mut projection);
+            Vec3::from(transform.forward())
+        };
+
+        let upright = forward_after_yaw(0.0, true);
+        assert!(upright.x < 0.0);
+        assert!(forward_after_yaw(PI, true).abs_diff_eq(upright, 1e-5));
+        // without the flip, the same drag turns the other way when upside down
+        assert!(forward_after_yaw(PI, false).x > 0.0);
+    }
+
+    #[test]
+    fn pitch_eases_out_toward_the_pole() {
+        let mut orbit = OrbitCamera::new(Vec3::ZERO, 5.0);
+        orbit.lock_up_axis = true;
+        orbit.pole_easing_margin = Some(0.5);
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        orbit.update_transform(&mut transform, &mut projection);
+
+        let mut pitch = 0.0;
+        let mut steps = Vec::new();
+        for _ in 0..30 {
+            orbit.orbit(0.0, -0.1, 0.0);
+            orbit.update_transform(&mut transform, &mut projection);
+            let next = OrbitAngles::from_rotation(transform.rotation).pitch;
+            steps.push(next - pitch);
+            pitch = next;
+        }
+        assert!((steps[0] - 0.1).abs() < 1e-5);
+        assert!(steps.windows(2).all(|pair| pair[1] <= pair[0] + 1e-6));
+        assert!(steps[29] < 0.01);
+        assert!(pitch < FRAC_PI_2);
+    }
+
+    #[test]
+    fn min_elevation_keeps_the_camera_above_the_focus() {
+        for lock_up_axis in [false, true] {
+            let mut orbit =
+                OrbitCamera::new(Vec3::new(1.0, 2.0, 3.0), 5.0).with_orbit(0.3, 0.5, 0.0);
+            orbit.lock_up_axis = lock_up_axis;
+            orbit.min_elevation = Some(0.2);
+            let mut transform = Transform::default();
+            let mut projection = Projection::default();
+            orbit.update_transform(&mut transform, &mut projection);
+
+            // orbit downward, well past the horizontal plane
+            for _ in 0..10 {
+                orbit.orbit(0.0, -0.3, 0.0);
+                orbit.update_transform(&mut transform, &mut projection);
+                let offset = transform.translation - orbit.focus;
+                let elevation = (offset.y / offset.length()).asin();
+                assert!(elevation >= 0.2 - 1e-4);
+                assert!((offset.length() - 5.0).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn unrolled_orbit_pitches_about_the_horizon() {
+        let forward_after_pitch = |orbit_unrolled: bool| {
+            let mut orbit = OrbitCamera::new(Vec3::ZERO, 5.0).with_orbit(0.0, 0.0, 0.8);
+            orbit.orbit_unrolled = orbit_unrolled;
+            let mut transform = Transform::default();
+            let mut projection = Projection::default();
+            orbit.update_transform(&mut transform, &mut projection);
+            orbit.orbit(0.0, 0.3, 0.0);
+            orbit.update_transform(&mut transform, &mut projection);
+            Vec3::from(transform.forward())
+        };
+
+        // the view tilts straight down, as if the camera weren't rolled
+        let unrolled = forward_after_pitch(true);
+        assert!(unrolled.abs_diff_eq(Vec3::new(0.0, -0.3f32.sin(), -0.3f32.cos()), 1e-5));
+        // about the rolled axis, the view also swings sideways
+        assert!(forward_after_pitch(false).x.abs() > 0.1);
+    }
+
+    #[test]
+    fn paging_right_moves_the_focus_by_one_viewport_width() {
+        let camera = test_camera();
+        let perspective = Projection::Perspective(PerspectiveProjection {
+            aspect_ratio: 800.0 / 600.0,
+            ..default()
+        });
+        let orthographic = Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical(2.0),
+            ..default()
+        });
+        let perspective_width = 2.0 * 5.0 * (PI / 8.0).tan() * 800.0 / 600.0;
+        for (mut projection, width) in [
+            (perspective, perspective_width),
+            (orthographic, 5.0 * 2.0 * 800.0 / 600.0),
+        ] {
+            let mut orbit = OrbitCamera::new(Vec3::ZERO, 5.0);
+            let mut transform = Transform::default();
+            orbit.update_transform(&mut transform, &mut projection);
+
+            orbit.pan_screens(Vec2::X, &camera, &projection);
+            orbit.update_transform(&mut transform, &mut projection);
+            assert!(orbit.focus.abs_diff_eq(Vec3::new(width, 0.0, 0.0), 1e-4));
+        }
+    }
+
+    #[test]
+    fn fixed_camera_height_keeps_the_altitude_while_orbiting() {
+        let mut orbit = OrbitCamera::new(Vec3::new(1.0, 0.5, -2.0), 10.0);
+        orbit.fixed_camera_height = Some(4.0);
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        for (delta_yaw, delta_pitch) in [(0.0, 0.0), (0.5, 0.2), (1.0, -0.6), (-2.0, 0.4)] {
+            orbit.orbit(delta_yaw, delta_pitch, 0.0);
+            orbit.update_transform(&mut transform, &mut projection);
+            assert!((transform.translation.y - 4.0).abs() < 1e-4);
+            assert!((transform.translation.distance(orbit.focus) - 10.0).abs() < 1e-4);
+        }
+
+        // zooming in closer than the height difference stops at the height
+        orbit.radius = 1.0;
+        orbit.update_transform(&mut transform, &mut projection);
+        assert!((orbit.radius - 3.5).abs() < 1e-5);
+        assert!((transform.translation.y - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn view_projection_matches_the_updated_camera() {
+        let mut orbit = OrbitCamera::new(Vec3::new(1.0, 2.0, 3.0), 5.0);
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        orbit.update_transform(&mut transform, &mut projection);
+        orbit.orbit(0.4, 0.3, 0.1);
+        orbit.pan(Vec2::new(1.0, -0.5));
+
+        let view_projection =
+            orbit.view_projection(&transform, &projection, Vec2::new(800.0, 600.0));
+        orbit.update_transform(&mut transform, &mut projection);
+        let view = GlobalTransform::from(transform).compute_matrix().inverse();
+        let expected = Mat4::perspective_infinite_reverse_rh(PI / 4.0, 800.0 / 600.0, 0.1) * view;
+        assert!(view_projection.abs_diff_eq(expected, 1e-5));
+        // the focus is at the center of the view
+        let focus = view_projection.project_point3(orbit.focus);
+        assert!(focus.truncate().abs_diff_eq(Vec2::ZERO, 1e-5));
+    }
+
+    #[test]
+    fn zooming_through_the_focus_flips_the_camera() {
+        let mut orbit = OrbitCamera::new(Vec3::new(1.0, 2.0, 3.0), 2.0);
+        orbit.zoom_through_focus = true;
+        orbit.radius_limit = Some(1.0)..=None;
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        orbit.update_transform(&mut transform, &mut projection);
+
+        // half a unit past the limit
+        orbit.zoom(0.25);
+        orbit.update_transform(&mut transform, &mut projection);
+        assert!((orbit.radius - 1.5).abs() < 1e-5);
+        assert!(transform
+            .translation
+            .abs_diff_eq(orbit.focus + Vec3::new(0.0, 0.0, -1.5), 1e-5));
+        assert!(Vec3::from(transform.forward()).abs_diff_eq(Vec3::Z, 1e-5));
+
+        // zooming in further moves on away from the focus on the other side
+        orbit.zoom(0.5);
+        orbit.update_transform(&mut transform, &mut projection);
+        assert!(transform
+            .translation
+            .abs_diff_eq(orbit.focus + Vec3::new(0.0, 0.0, -3.0), 1e-4));
     }
 }
@@ -0,0 +1,140 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::OrbitCamera;
+
+/// A snapshot of everything needed to restore the view of an `OrbitCamera`.
+///
+/// Useful for saving and loading views, undo/redo, or bookmarks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OrbitCameraPose {
+    /// The focus point around which the camera orbits.
+    pub focus: Vec3,
+    /// The distance from the camera to the focus point.
+    pub radius: f32,
+    /// The orientation of the camera.
+    pub rotation: Quat,
+    pub lock_up_axis: bool,
+}
+
+impl OrbitCamera {
+    /// Captures the current pose of the camera.
+    pub fn pose(&self, transform: &Transform) -> OrbitCameraPose {
+        OrbitCameraPose {
+            focus: self.focus,
+            radius: self.radius,
+            rotation: transform.rotation,
+            lock_up_axis: self.lock_up_axis,
+        }
+    }
+
+    /// The pose the camera will have once its pending deltas have been applied by `update_transform`.
+    pub fn pending_pose(&self, transform: &Transform, projection: &Projection) -> OrbitCameraPose {
+        let mut property = self.clone();
+        let mut transform = *transform;
+        let mut projection = projection.clone();
+        property.update_transform(&mut transform, &mut projection);
+        property.pose(&transform)
+    }
+
+    /// Restores a pose previously captured with [`OrbitCamera::pose`], discarding pending deltas.
+    pub fn apply_pose(
+        &mut self,
+        pose: &OrbitCameraPose,
+        transform: &mut Transform,
+        projection: &mut Projection,
+    ) {
+        self.focus = pose.focus;
+        self.radius = pose.radius;
+        self.lock_up_axis = pose.lock_up_axis;
+        self.reset_rotation_and_pan_deltas();
+        transform.rotation = pose.rotation;
+        self.update_transform(transform, projection);
+    }
+}
+
+/// System parameter listing all orbit cameras with their current poses, e.g. for a camera manager UI.
+#[derive(SystemParam)]
+pub struct OrbitPoses<'w, 's> {
+    camera_q: Query<'w, 's, (Entity, &'static OrbitCamera, &'static Transform)>,
+}
+
+impl OrbitPoses<'_, '_> {
+    pub fn collect(&self) -> Vec<(Entity, OrbitCameraPose)> {
+        self.camera_q
+            .iter()
+            .map(|(entity, property, transform)| (entity, property.pose(transform)))
+            .collect()
+    }
+}
+
+/// Lists all orbit cameras with their current poses, for use outside of systems.
+pub fn collect_orbit_poses(world: &World) -> Vec<(Entity, OrbitCameraPose)> {
+    world
+        .iter_entities()
+        .filter_map(|entity| {
+            let property = entity.get::<OrbitCamera>()?;
+            let transform = entity.get::<Transform>()?;
+            Some((entity.id(), property.pose(transform)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::SystemState;
+
+    #[test]
+    fn applying_a_captured_pose_restores_the_transform() {
+        let mut orbit = OrbitCamera::new(Vec3::new(1.0, 0.0, -2.0), 5.0).with_orbit(0.4, 0.3, 0.1);
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        orbit.update_transform(&mut transform, &mut projection);
+        let pose = orbit.pose(&transform);
+        let expected = transform;
+
+        orbit.orbit(1.0, -0.5, 0.2);
+        orbit.pan(Vec2::new(3.0, 1.0));
+        orbit.radius = 12.0;
+        orbit.update_transform(&mut transform, &mut projection);
+        assert!(!transform
+            .translation
+            .abs_diff_eq(expected.translation, 1e-3));
+
+        orbit.apply_pose(&pose, &mut transform, &mut projection);
+        assert_eq!(orbit.focus, pose.focus);
+        assert_eq!(orbit.radius, pose.radius);
+        assert!(transform
+            .translation
+            .abs_diff_eq(expected.translation, 1e-4));
+        assert!(transform.rotation.abs_diff_eq(expected.rotation, 1e-5));
+    }
+
+    #[test]
+    fn all_camera_poses_are_collected() {
+        let mut world = World::new();
+        let mut expected: Vec<(Entity, OrbitCameraPose)> = (0..3)
+            .map(|i| {
+                let orbit = OrbitCamera::new(Vec3::X * i as f32, 1.0 + i as f32);
+                let transform = Transform::from_rotation(Quat::from_rotation_y(0.1 * i as f32));
+                let pose = orbit.pose(&transform);
+                (world.spawn((orbit, transform)).id(), pose)
+            })
+            .collect();
+        // not a camera
+        world.spawn(Transform::default());
+        expected.sort_by_key(|(entity, _)| *entity);
+
+        let mut poses = collect_orbit_poses(&world);
+        poses.sort_by_key(|(entity, _)| *entity);
+        assert_eq!(poses, expected);
+
+        let mut state = SystemState::<OrbitPoses>::new(&mut world);
+        let mut poses = state.get(&world).collect();
+        poses.sort_by_key(|(entity, _)| *entity);
+        assert_eq!(poses, expected);
+    }
+}
@@ -0,0 +1,105 @@
+use std::{fmt, sync::Arc};
+
+use bevy::prelude::*;
+
+/// A function mapping raw input magnitude to the magnitude used for camera deltas.
+#[derive(Clone, Default)]
+pub enum ResponseCurve {
+    /// Passes the input through unchanged.
+    #[default]
+    Linear,
+    /// Raises the input magnitude to the given power, keeping the sign.
+    Power(f32),
+    /// A user-supplied function, e.g. sampled from a curve authored in a design tool.
+    Custom(Arc<dyn Fn(f32) -> f32 + Send + Sync>),
+}
+
+impl fmt::Debug for ResponseCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResponseCurve::Linear => write!(f, "Linear"),
+            ResponseCurve::Power(exponent) => f.debug_tuple("Power").field(exponent).finish(),
+            ResponseCurve::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+impl ResponseCurve {
+    pub fn custom(curve: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        ResponseCurve::Custom(Arc::new(curve))
+    }
+
+    pub fn apply(&self, value: f32) -> f32 {
+        match self {
+            ResponseCurve::Linear => value,
+            ResponseCurve::Power(exponent) => value.signum() * value.abs().powf(*exponent),
+            ResponseCurve::Custom(curve) => curve(value),
+        }
+    }
+
+    /// Applies the curve to the length of `value`, keeping its direction.
+    pub fn apply_vec2(&self, value: Vec2) -> Vec2 {
+        let length = value.length();
+        if length == 0.0 {
+            return value;
+        }
+        value * self.apply(length) / length
+    }
+}
+
+/// Resource holding the response curves consulted by the control systems, linear by default.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ResponseCurves {
+    /// Applied to the scroll amount of each scroll event.
+    pub zoom: ResponseCurve,
+    /// Applied to the per-frame mouse motion in pixels while panning.
+    pub pan: ResponseCurve,
+    /// Applied to the per-frame mouse motion in pixels while rotating.
+    pub rotation: ResponseCurve,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::mouse::MouseMotion;
+
+    use crate::{
+        controls::{pixel_delta_to_orbit, OrbitControlsConfig},
+        test_util::{controls_app, next_frame, spawn_camera, VIEWPORT_SIZE},
+        OrbitAngles, OrbitCamera,
+    };
+
+    #[test]
+    fn curves_keep_the_sign_and_direction() {
+        assert_eq!(ResponseCurve::Power(2.0).apply(-3.0), -9.0);
+        let scaled = ResponseCurve::custom(|value| 2.0 * value).apply_vec2(Vec2::new(3.0, 4.0));
+        assert!(scaled.abs_diff_eq(Vec2::new(6.0, 8.0), 1e-6));
+        assert_eq!(ResponseCurve::Power(2.0).apply_vec2(Vec2::ZERO), Vec2::ZERO);
+    }
+
+    #[test]
+    fn custom_rotation_curve_scales_the_orbit() {
+        let mut app = controls_app();
+        app.world.resource_mut::<ResponseCurves>().rotation =
+            ResponseCurve::custom(|pixels| 2.0 * pixels);
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world.entity_mut(camera).insert(OrbitAngles::default());
+        next_frame(&mut app);
+
+        app.world
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.world.send_event(MouseMotion {
+            delta: Vec2::new(-60.0, 0.0),
+        });
+        next_frame(&mut app);
+        let rotation_speed = app.world.resource::<OrbitControlsConfig>().rotation_speed;
+        let expected = pixel_delta_to_orbit(
+            Vec2::new(-120.0, 0.0),
+            VIEWPORT_SIZE.as_vec2(),
+            rotation_speed,
+        );
+        let yaw = app.world.get::<OrbitAngles>(camera).unwrap().yaw;
+        assert!((yaw - expected.x).abs() < 1e-5);
+    }
+}
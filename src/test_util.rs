@@ -0,0 +1,72 @@
+//! Helpers for running the camera in tests, without a window or renderer.
+
+use std::time::Duration;
+
+use bevy::{
+    input::mouse::{MouseMotion, MouseWheel},
+    prelude::*,
+    render::camera::Viewport,
+    time::TimeUpdateStrategy,
+};
+
+use crate::{controls::OrbitControlsPlugin, OrbitCamera, OrbitCameraPlugin};
+
+/// The time step of the apps created with [`test_app`]. The first update has a time step of zero.
+pub(crate) const FRAME_TIME: f32 = 1.0 / 60.0;
+
+/// The physical viewport size of [`test_camera`].
+pub(crate) const VIEWPORT_SIZE: UVec2 = UVec2::new(800, 600);
+
+/// A camera with a viewport of `VIEWPORT_SIZE`, so its physical viewport size is known without a render target.
+///
+/// The logical viewport size still needs the target info computed by the renderer and is `None`.
+pub(crate) fn test_camera() -> Camera {
+    Camera {
+        viewport: Some(Viewport {
+            physical_size: VIEWPORT_SIZE,
+            ..default()
+        }),
+        ..default()
+    }
+}
+
+/// An app running the camera systems with a fixed time step.
+pub(crate) fn test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            FRAME_TIME,
+        )))
+        .add_plugins(OrbitCameraPlugin::default());
+    app
+}
+
+/// Like [`test_app`], with the controls and the input they read, which is driven by the tests.
+pub(crate) fn controls_app() -> App {
+    let mut app = test_app();
+    app.init_resource::<ButtonInput<KeyCode>>()
+        .init_resource::<ButtonInput<MouseButton>>()
+        .add_event::<MouseWheel>()
+        .add_event::<MouseMotion>()
+        .add_plugins(OrbitControlsPlugin::<()>::default());
+    app
+}
+
+/// Runs a frame, then clears the keys and buttons pressed and released in it like bevy's input systems would.
+pub(crate) fn next_frame(app: &mut App) {
+    app.update();
+    app.world.resource_mut::<ButtonInput<KeyCode>>().clear();
+    app.world.resource_mut::<ButtonInput<MouseButton>>().clear();
+}
+
+/// Spawns a camera with the default transform and perspective projection.
+pub(crate) fn spawn_camera(app: &mut App, orbit: OrbitCamera) -> Entity {
+    app.world
+        .spawn((
+            orbit,
+            Transform::default(),
+            Projection::default(),
+            test_camera(),
+        ))
+        .id()
+}
@@ -0,0 +1,108 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// Resource selecting the clock the time-based camera systems use, e.g. roll, auto-rotate and smoothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Resource)]
+pub enum TimeSource {
+    /// The generic `Time` of the schedule, virtual time in `Update` and fixed time in `FixedUpdate`.
+    #[default]
+    Generic,
+    /// Real time, so the camera keeps moving while the game is paused.
+    Real,
+    /// Virtual time, so the camera respects pausing and time scaling of the game.
+    Virtual,
+}
+
+/// Component overriding the [`TimeSource`] for a single camera, e.g. a live camera next to one showing
+/// a paused sub-scene in split-screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct TimeSourceOverride(pub TimeSource);
+
+/// System parameter reading the clock selected by [`TimeSource`].
+#[derive(SystemParam)]
+pub struct OrbitTime<'w> {
+    source: Res<'w, TimeSource>,
+    time: Res<'w, Time>,
+    real: Res<'w, Time<Real>>,
+    virtual_time: Res<'w, Time<Virtual>>,
+}
+
+impl OrbitTime<'_> {
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta_seconds_for(None)
+    }
+
+    /// The delta of the clock selected by `source_override` if present, otherwise by the [`TimeSource`] resource.
+    pub fn delta_seconds_for(&self, source_override: Option<&TimeSourceOverride>) -> f32 {
+        match source_override.map_or(*self.source, |source_override| source_override.0) {
+            TimeSource::Generic => self.time.delta_seconds(),
+            TimeSource::Real => self.real.delta_seconds(),
+            TimeSource::Virtual => self.virtual_time.delta_seconds(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        controls::OrbitControlsConfig,
+        test_util::{controls_app, next_frame, spawn_camera, FRAME_TIME},
+        OrbitAngles, OrbitCamera,
+    };
+
+    #[test]
+    fn roll_follows_the_selected_clock_while_paused() {
+        let roll_while_paused = |source: TimeSource| {
+            let mut app = controls_app();
+            app.insert_resource(source);
+            let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+            app.world.entity_mut(camera).insert(OrbitAngles::default());
+            next_frame(&mut app);
+            app.world.resource_mut::<Time<Virtual>>().pause();
+            app.world
+                .resource_mut::<ButtonInput<KeyCode>>()
+                .press(KeyCode::KeyQ);
+            for _ in 0..3 {
+                next_frame(&mut app);
+            }
+            app.world.get::<OrbitAngles>(camera).unwrap().roll.abs()
+        };
+
+        assert_eq!(roll_while_paused(TimeSource::Virtual), 0.0);
+        let roll_speed = OrbitControlsConfig::default().roll_speed;
+        assert!((roll_while_paused(TimeSource::Real) - 3.0 * FRAME_TIME * roll_speed).abs() < 1e-4);
+    }
+
+    #[test]
+    fn only_the_real_time_camera_keeps_smoothing_while_paused() {
+        let mut app = controls_app();
+        // the zoom toward the target radius is spread over frames by the elapsed time
+        app.world
+            .resource_mut::<OrbitControlsConfig>()
+            .max_zoom_speed = Some(1.0);
+        let spawn = |app: &mut App, source: TimeSource| {
+            let camera = spawn_camera(app, OrbitCamera::new(Vec3::ZERO, 4.0));
+            app.world
+                .entity_mut(camera)
+                .insert(TimeSourceOverride(source));
+            camera
+        };
+        let real = spawn(&mut app, TimeSource::Real);
+        let virtual_camera = spawn(&mut app, TimeSource::Virtual);
+        next_frame(&mut app);
+
+        app.world.resource_mut::<Time<Virtual>>().pause();
+        for camera in [real, virtual_camera] {
+            app.world
+                .get_mut::<OrbitCamera>(camera)
+                .unwrap()
+                .set_radius_smoothed(8.0);
+        }
+        for _ in 0..10 {
+            next_frame(&mut app);
+        }
+        let radius = |camera: Entity| app.world.get::<OrbitCamera>(camera).unwrap().radius;
+        assert!(radius(real) > 4.5);
+        assert_eq!(radius(virtual_camera), 4.0);
+    }
+}
@@ -0,0 +1,310 @@
+use bevy::prelude::*;
+
+use crate::{
+    controls::ControlActivity,
+    pose::OrbitCameraPose,
+    time::{OrbitTime, TimeSourceOverride},
+    util::{calculate_framing_radius, from_direction_with_up_axis},
+    FreezeTransform, OrbitCamera,
+};
+
+/// Component that smoothly moves an `OrbitCamera` from one pose to another.
+///
+/// The component removes itself once the transition has finished.
+#[derive(Debug, Clone, Component)]
+pub struct CameraTransition {
+    pub from: OrbitCameraPose,
+    pub to: OrbitCameraPose,
+    /// The duration of the transition in seconds.
+    pub duration: f32,
+    pub elapsed: f32,
+    /// Stops the transition where it is when the camera controls are used,
+    /// instead of overriding the input until it has finished.
+    ///
+    /// Input is read from the [`ControlActivity`] of the camera, so programmatic motion like
+    /// `AutoRotate` doesn't cancel the transition.
+    pub cancel_on_input: bool,
+}
+
+impl CameraTransition {
+    pub fn new(from: OrbitCameraPose, to: OrbitCameraPose, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            elapsed: 0.0,
+            cancel_on_input: false,
+        }
+    }
+
+    pub fn with_cancel_on_input(mut self, cancel_on_input: bool) -> Self {
+        self.cancel_on_input = cancel_on_input;
+        self
+    }
+
+    /// The eased progress of the transition in the range `0.0..=1.0`.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        let t = (self.elapsed / self.duration).clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Samples the interpolated pose at the current progress.
+    pub fn sample(&self) -> OrbitCameraPose {
+        let t = self.progress();
+        OrbitCameraPose {
+            focus: self.from.focus.lerp(self.to.focus, t),
+            radius: f32::lerp(self.from.radius, self.to.radius, t),
+            rotation: self.from.rotation.slerp(self.to.rotation, t),
+            lock_up_axis: self.to.lock_up_axis,
+        }
+    }
+}
+
+type TransitionCameraQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut OrbitCamera,
+        &'static mut Transform,
+        &'static mut CameraTransition,
+        Option<&'static TimeSourceOverride>,
+        Option<&'static ControlActivity>,
+    ),
+    // a transition of a frozen camera is paused, and continues once it's unfrozen
+    Without<FreezeTransform>,
+>;
+
+pub(crate) fn update_transition(
+    mut commands: Commands,
+    time: OrbitTime,
+    mut camera_q: TransitionCameraQuery,
+) {
+    for (entity, mut property, mut transform, mut transition, source_override, activity) in
+        camera_q.iter_mut()
+    {
+        if transition.cancel_on_input && activity.is_some_and(ControlActivity::any) {
+            commands.entity(entity).remove::<CameraTransition>();
+            continue;
+        }
+        transition.elapsed += time.delta_seconds_for(source_override);
+        let pose = transition.sample();
+        property.focus = pose.focus;
+        property.radius = pose.radius;
+        property.lock_up_axis = pose.lock_up_axis;
+        property.reset_rotation_and_pan_deltas();
+        transform.rotation = pose.rotation;
+        if transition.is_finished() {
+            commands.entity(entity).remove::<CameraTransition>();
+        }
+    }
+}
+
+/// Event to smoothly move the focus of a camera to a sphere and zoom until it fits into the view.
+#[derive(Debug, Clone, Event)]
+pub struct ZoomToFrame {
+    /// The camera entity, which needs an `OrbitCamera`.
+    pub camera: Entity,
+    /// The center of the sphere to frame.
+    pub center: Vec3,
+    /// The radius of the sphere to frame.
+    pub radius: f32,
+    /// The duration of the transition in seconds.
+    pub duration: f32,
+    /// Frames the sphere even if the radius is outside the `radius_limit` of the camera.
+    /// The limits apply again on the next zoom.
+    pub ignore_limits: bool,
+}
+
+pub(crate) fn zoom_to_frame(
+    mut commands: Commands,
+    mut events: EventReader<ZoomToFrame>,
+    mut camera_q: Query<(&mut OrbitCamera, &Transform, &Camera, &Projection)>,
+) {
+    for event in events.read() {
+        let Ok((mut property, transform, camera, projection)) = camera_q.get_mut(event.camera)
+        else {
+            continue;
+        };
+        let Some(radius) = calculate_framing_radius(camera, projection, event.radius) else {
+            continue;
+        };
+        if property.relative_zoom_limit.is_some() {
+            // relative zoom limits are relative to the framed view from now on
+            property.state.zoom_baseline = Some(radius);
+        }
+        let from = property.pose(transform);
+        let to = OrbitCameraPose {
+            focus: event.center,
+            radius: if event.ignore_limits {
+                radius
+            } else {
+                property.clamp_radius(radius)
+            },
+            ..from
+        };
+        commands
+            .entity(event.camera)
+            .try_insert(CameraTransition::new(from, to, event.duration));
+    }
+}
+
+/// Event to fly a camera to a focus, view direction and radius in one eased transition, e.g. for a "go here" command.
+#[derive(Debug, Clone, Event)]
+pub struct FlyTo {
+    /// The camera entity, which needs an `OrbitCamera`.
+    pub camera: Entity,
+    /// The focus to fly to.
+    pub focus: Vec3,
+    /// The direction the camera looks in on arrival, from the camera toward the focus.
+    pub look_direction: Vec3,
+    /// The radius on arrival, clamped to the limits of the camera.
+    pub radius: f32,
+    /// The duration of the flight in seconds.
+    pub duration: f32,
+    /// Stops the flight when the camera controls are used, see [`CameraTransition::cancel_on_input`].
+    pub cancel_on_input: bool,
+}
+
+impl FlyTo {
+    pub fn new(
+        camera: Entity,
+        focus: Vec3,
+        look_direction: Vec3,
+        radius: f32,
+        duration: f32,
+    ) -> Self {
+        Self {
+            camera,
+            focus,
+            look_direction,
+            radius,
+            duration,
+            cancel_on_input: false,
+        }
+    }
+
+    pub fn with_cancel_on_input(mut self, cancel_on_input: bool) -> Self {
+        self.cancel_on_input = cancel_on_input;
+        self
+    }
+}
+
+pub(crate) fn fly_to(
+    mut commands: Commands,
+    mut events: EventReader<FlyTo>,
+    camera_q: Query<(&OrbitCamera, &Transform)>,
+) {
+    for event in events.read() {
+        let Ok((property, transform)) = camera_q.get(event.camera) else {
+            continue;
+        };
+        let from = property.pose(transform);
+        let to = OrbitCameraPose {
+            focus: event.focus,
+            radius: property.clamp_radius(event.radius),
+            rotation: from_direction_with_up_axis(
+                event.look_direction,
+                property.up_axis.unwrap_or_default(),
+            ),
+            ..from
+        };
+        // the smoothstep easing of the transition arrives without overshooting
+        commands.entity(event.camera).try_insert(
+            CameraTransition::new(from, to, event.duration)
+                .with_cancel_on_input(event.cancel_on_input),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        auto_rotate::AutoRotate,
+        test_util::{spawn_camera, test_app},
+    };
+
+    fn fly(app: &mut App, camera: Entity, cancel_on_input: bool) {
+        app.world.send_event(
+            FlyTo::new(camera, Vec3::new(3.0, 1.0, 0.0), Vec3::X, 8.0, 0.5)
+                .with_cancel_on_input(cancel_on_input),
+        );
+    }
+
+    #[test]
+    fn flying_arrives_at_the_target_pose() {
+        let mut app = test_app();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.update();
+
+        fly(&mut app, camera, false);
+        // half a second and a frame or two of slack for the start of the flight
+        for _ in 0..32 {
+            app.update();
+        }
+        assert!(app.world.get::<CameraTransition>(camera).is_none());
+        let orbit = app.world.get::<OrbitCamera>(camera).unwrap();
+        let transform = app.world.get::<Transform>(camera).unwrap();
+        assert!(orbit.focus.abs_diff_eq(Vec3::new(3.0, 1.0, 0.0), 1e-5));
+        assert_eq!(orbit.radius, 8.0);
+        assert!(Vec3::from(transform.forward()).abs_diff_eq(Vec3::X, 1e-5));
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(-5.0, 1.0, 0.0), 1e-4));
+    }
+
+    #[test]
+    fn control_activity_cancels_the_flight() {
+        let mut app = test_app();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world
+            .entity_mut(camera)
+            .insert(ControlActivity::default());
+        app.update();
+
+        fly(&mut app, camera, true);
+        for _ in 0..5 {
+            app.update();
+        }
+        let focus = app.world.get::<OrbitCamera>(camera).unwrap().focus;
+        assert!(focus.x > 0.0);
+
+        app.world
+            .get_mut::<ControlActivity>(camera)
+            .unwrap()
+            .rotating = true;
+        app.update();
+        app.update();
+        assert!(app.world.get::<CameraTransition>(camera).is_none());
+        // stopped where it was
+        let orbit = app.world.get::<OrbitCamera>(camera).unwrap();
+        assert_eq!(orbit.focus, focus);
+    }
+
+    #[test]
+    fn auto_rotate_doesnt_cancel_the_flight() {
+        let mut app = test_app();
+        let camera = spawn_camera(&mut app, OrbitCamera::new(Vec3::ZERO, 5.0));
+        app.world
+            .entity_mut(camera)
+            .insert((AutoRotate::new(1.0), ControlActivity::default()));
+        app.update();
+
+        fly(&mut app, camera, true);
+        for _ in 0..32 {
+            app.update();
+        }
+        let orbit = app.world.get::<OrbitCamera>(camera).unwrap();
+        assert!(orbit.focus.abs_diff_eq(Vec3::new(3.0, 1.0, 0.0), 1e-5));
+        assert_eq!(orbit.radius, 8.0);
+    }
+}
@@ -1,11 +1,16 @@
-use crate::OrbitCamera;
-use bevy::prelude::*;
+use crate::{OrbitCamera, UpAxis};
+use bevy::{
+    prelude::*,
+    render::camera::{CameraProjection, ScalingMode},
+};
 
 /// Calculates the scaling factor for panning operations.
 ///
 /// This function computes a scaling factor for panning movements based on the current projection type and attributes of the camera.
 /// The scaling factor takes into account the camera's viewport size, projection type (perspective or orthographic),
 /// and other camera properties such as focal length and aspect ratio.
+/// In both projections the scaling maps one pixel to the world distance it covers on the focus plane,
+/// so the point under the cursor stays under the cursor while panning.
 ///
 /// # Parameters
 /// * `camera` - The camera instance to reference for physical viewport size.
@@ -13,7 +18,9 @@ use bevy::prelude::*;
 /// * `property` - The `PanOrbitCamera` properties, containing attributes like camera focal length.
 ///
 /// # Returns
-/// Returns a `Vec2` representing the scaling factor to be applied on the X and Y axes for panning operations.
+/// Returns a `Vec2` representing the scaling factor to be applied on the X and Y axes for panning operations,
+/// or `None` if the viewport size is unknown or the projection is degenerate, e.g. with a zero field of view
+/// or area, so panning does nothing instead of moving the focus by NaN or infinity.
 pub fn calculate_pan_scaling_factor(
     camera: &Camera,
     projection: &Projection,
@@ -23,18 +30,77 @@ pub fn calculate_pan_scaling_factor(
         let viewport_size = viewport_size.as_vec2();
         let factor = match projection {
             Projection::Perspective(p) => {
-                property.radius * p.fov * Vec2::new(p.aspect_ratio, 1.0) / viewport_size
+                // the exact height of the view at the focus distance, so a drag moves the focus plane
+                // by the same screen distance as the orthographic area does
+                let focus_plane_height = 2.0 * property.radius * (0.5 * p.fov).tan();
+                focus_plane_height * Vec2::new(p.aspect_ratio, 1.0) / viewport_size
             }
             Projection::Orthographic(p) => {
-                Vec2::new(p.area.width(), p.area.height()) / viewport_size
+                // `p.area` is only refreshed by bevy's camera system after `update_transform`
+                // has written the new scale, so recompute it from the radius to avoid lagging a frame.
+                let logical_size = camera.logical_viewport_size().unwrap_or(viewport_size);
+                calculate_orthographic_area(p, property.radius, logical_size) / viewport_size
             }
         };
-        Some(factor)
+        (factor.is_finite() && factor.cmpgt(Vec2::ZERO).all()).then_some(factor)
     } else {
         None
     }
 }
 
+/// Calculates the size of the visible area of an orthographic projection.
+///
+/// Mirrors the computation bevy performs in `OrthographicProjection::update`, but uses the given `scale`
+/// instead of the one stored in the projection, so the result is valid before the projection has been updated.
+///
+/// # Parameters
+/// * `projection` - The orthographic projection providing the scaling mode.
+/// * `scale` - The scale to apply, usually the `OrbitCamera` radius.
+/// * `viewport_size` - The logical size of the viewport.
+///
+/// # Returns
+/// Returns a `Vec2` with the width and height of the visible area in world units.
+pub fn calculate_orthographic_area(
+    projection: &OrthographicProjection,
+    scale: f32,
+    viewport_size: Vec2,
+) -> Vec2 {
+    let (width, height) = (viewport_size.x, viewport_size.y);
+    let size = match projection.scaling_mode {
+        ScalingMode::WindowSize(pixel_scale) => {
+            Vec2::new(width / pixel_scale, height / pixel_scale)
+        }
+        ScalingMode::AutoMin {
+            min_width,
+            min_height,
+        } => {
+            if width * min_height > min_width * height {
+                Vec2::new(width * min_height / height, min_height)
+            } else {
+                Vec2::new(min_width, height * min_width / width)
+            }
+        }
+        ScalingMode::AutoMax {
+            max_width,
+            max_height,
+        } => {
+            if width * max_height < max_width * height {
+                Vec2::new(width * max_height / height, max_height)
+            } else {
+                Vec2::new(max_width, height * max_width / width)
+            }
+        }
+        ScalingMode::FixedVertical(viewport_height) => {
+            Vec2::new(width * viewport_height / height, viewport_height)
+        }
+        ScalingMode::FixedHorizontal(viewport_width) => {
+            Vec2::new(viewport_width, height * viewport_width / width)
+        }
+        ScalingMode::Fixed { width, height } => Vec2::new(width, height),
+    };
+    scale * size
+}
+
 /// Calculates the rotation quaternion from a direction and an up vector.
 pub fn from_direction(direction: Vec3, up: Vec3) -> Quat {
     let back = -direction.try_normalize().unwrap_or(Vec3::NEG_Z);
@@ -46,3 +112,438 @@ pub fn from_direction(direction: Vec3, up: Vec3) -> Quat {
     let up = back.cross(right);
     Quat::from_mat3(&Mat3::from_cols(right, up, back))
 }
+
+/// Calculates the rotation quaternion from a direction and the vertical axis of an [`UpAxis`].
+pub fn from_direction_with_up_axis(direction: Vec3, up_axis: UpAxis) -> Quat {
+    from_direction(direction, up_axis.vector())
+}
+
+/// Calculates the world-space ray under the cursor from the orbit camera.
+///
+/// Unlike `Camera::viewport_to_world`, the ray is built from the transform and projection the `OrbitCamera`
+/// will produce this frame, so it doesn't lag behind by a frame when used before `update_transform` has run.
+///
+/// # Parameters
+/// * `orbit` - The `OrbitCamera` properties, including pending deltas.
+/// * `transform` - The current transform of the camera.
+/// * `camera` - The camera instance to reference for the logical viewport size.
+/// * `projection` - The projection used by the camera.
+/// * `cursor_pos` - The cursor position in logical viewport coordinates.
+///
+/// # Returns
+/// Returns the `Ray3d` under the cursor, or `None` if the viewport size is unknown.
+pub fn cursor_ray(
+    orbit: &OrbitCamera,
+    transform: &Transform,
+    camera: &Camera,
+    projection: &Projection,
+    cursor_pos: Vec2,
+) -> Option<Ray3d> {
+    let viewport_size = camera.logical_viewport_size()?;
+    let mut orbit = orbit.clone();
+    let mut transform = *transform;
+    let mut projection = projection.clone();
+    orbit.update_transform(&mut transform, &mut projection);
+    projection.update(viewport_size.x, viewport_size.y);
+
+    // flip the y coordinate origin from the top to the bottom
+    let viewport_position = Vec2::new(cursor_pos.x, viewport_size.y - cursor_pos.y);
+    let ndc = viewport_position * 2.0 / viewport_size - Vec2::ONE;
+    ndc_ray(&transform, &projection, ndc)
+}
+
+/// Builds the world-space ray through a point in normalized device coordinates.
+fn ndc_ray(transform: &Transform, projection: &Projection, ndc: Vec2) -> Option<Ray3d> {
+    let ndc_to_world = transform.compute_matrix() * projection.get_projection_matrix().inverse();
+    let world_near_plane = ndc_to_world.project_point3(ndc.extend(1.0));
+    // an ndc with z = 0 returns NaNs
+    let world_far_plane = ndc_to_world.project_point3(ndc.extend(f32::EPSILON));
+    Direction3d::new(world_far_plane - world_near_plane)
+        .ok()
+        .map(|direction| Ray3d {
+            origin: world_near_plane,
+            direction,
+        })
+}
+
+/// Calculates the footprint of the camera frustum on a horizontal ground plane, e.g. for minimaps.
+///
+/// # Parameters
+/// * `camera` - The camera instance to reference for the logical viewport size.
+/// * `projection` - The projection used by the camera.
+/// * `orbit_transform` - The transform computed by the `OrbitCamera`.
+/// * `plane_y` - The height of the ground plane.
+///
+/// # Returns
+/// Returns the XZ coordinates of the bottom-left, bottom-right, top-right and top-left corners of the footprint,
+/// or `None` if the viewport size is unknown or any corner ray doesn't hit the plane.
+pub fn ground_footprint(
+    camera: &Camera,
+    projection: &Projection,
+    orbit_transform: &Transform,
+    plane_y: f32,
+) -> Option<[Vec2; 4]> {
+    ground_footprint_for_up_axis(camera, projection, orbit_transform, plane_y, UpAxis::Y)
+}
+
+/// Calculates the footprint of the camera frustum on the ground plane of an up axis, like [`ground_footprint`].
+///
+/// # Parameters
+/// * `camera` - The camera instance to reference for the logical viewport size.
+/// * `projection` - The projection used by the camera.
+/// * `orbit_transform` - The transform computed by the `OrbitCamera`.
+/// * `plane_height` - The height of the ground plane along the up axis.
+/// * `up_axis` - The up axis the ground plane is perpendicular to.
+///
+/// # Returns
+/// Returns the corners like `ground_footprint` in the coordinates of [`UpAxis::horizontal`].
+pub fn ground_footprint_for_up_axis(
+    camera: &Camera,
+    projection: &Projection,
+    orbit_transform: &Transform,
+    plane_height: f32,
+    up_axis: UpAxis,
+) -> Option<[Vec2; 4]> {
+    let viewport_size = camera.logical_viewport_size()?;
+    footprint_for_viewport(
+        projection,
+        orbit_transform,
+        viewport_size,
+        plane_height,
+        up_axis,
+    )
+}
+
+/// Intersects the corner rays of a viewport of `viewport_size` with the ground plane of `up_axis`.
+fn footprint_for_viewport(
+    projection: &Projection,
+    orbit_transform: &Transform,
+    viewport_size: Vec2,
+    plane_height: f32,
+    up_axis: UpAxis,
+) -> Option<[Vec2; 4]> {
+    let mut projection = projection.clone();
+    projection.update(viewport_size.x, viewport_size.y);
+    let corners = [
+        Vec2::new(-1.0, -1.0),
+        Vec2::new(1.0, -1.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(-1.0, 1.0),
+    ];
+    let mut footprint = [Vec2::ZERO; 4];
+    for (point, ndc) in footprint.iter_mut().zip(corners) {
+        let ray = ndc_ray(orbit_transform, &projection, ndc)?;
+        let up = up_axis.vector();
+        let distance = ray.intersect_plane(plane_height * up, Plane3d::new(up))?;
+        *point = up_axis.horizontal(ray.get_point(distance));
+    }
+    Some(footprint)
+}
+
+/// Maps a cursor position onto the arcball sphere of a viewport.
+///
+/// The sphere is centered on the viewport, not the window, so arcball rotation works in sub-viewports.
+///
+/// # Parameters
+/// * `cursor_pos` - The cursor position in logical window coordinates.
+/// * `viewport` - The logical viewport rect of the camera.
+///
+/// # Returns
+/// Returns a unit vector in view space, pointing toward the viewer at the viewport center.
+pub fn arcball_vector(cursor_pos: Vec2, viewport: Rect) -> Vec3 {
+    let radius = 0.5 * viewport.size().min_element();
+    let mut point = (cursor_pos - viewport.center()) / radius;
+    // window coordinates point down, view space points up
+    point.y = -point.y;
+    let length_squared = point.length_squared();
+    if length_squared <= 1.0 {
+        point.extend((1.0 - length_squared).sqrt())
+    } else {
+        point.normalize().extend(0.0)
+    }
+}
+
+/// Calculates the radius at which a sphere around the focus fits entirely into the view.
+///
+/// For orthographic projections the result is the scale, matching how `OrbitCamera` uses its radius.
+///
+/// # Parameters
+/// * `camera` - The camera instance to reference for the logical viewport size.
+/// * `projection` - The projection used by the camera.
+/// * `object_radius` - The radius of the sphere to frame.
+///
+/// # Returns
+/// Returns the radius, or `None` if the viewport size is unknown.
+pub fn calculate_framing_radius(
+    camera: &Camera,
+    projection: &Projection,
+    object_radius: f32,
+) -> Option<f32> {
+    let viewport_size = camera.logical_viewport_size()?;
+    let radius =
+        OrbitCamera::distance_for_screen_fraction(object_radius, 1.0, projection, viewport_size);
+    Some(radius)
+}
+
+/// Mirrors a transform across a plane, e.g. for a planar reflection camera.
+///
+/// The position and the view and up directions are mirrored. The result is a proper rotation,
+/// so the image rendered from it is mirrored horizontally like a reflection.
+///
+/// # Parameters
+/// * `transform` - The transform to mirror, usually the one computed by the `OrbitCamera`.
+/// * `plane_point` - Any point on the plane.
+/// * `plane_normal` - The normal of the plane.
+pub fn reflect_transform(
+    transform: &Transform,
+    plane_point: Vec3,
+    plane_normal: Vec3,
+) -> Transform {
+    let normal = plane_normal.try_normalize().unwrap_or(Vec3::Y);
+    let reflect = |v: Vec3| v - 2.0 * v.dot(normal) * normal;
+    let translation = plane_point + reflect(transform.translation - plane_point);
+    let forward = reflect(transform.rotation * Vec3::NEG_Z);
+    let up = reflect(transform.rotation * Vec3::Y);
+    Transform {
+        translation,
+        rotation: from_direction(forward, up),
+        scale: transform.scale,
+    }
+}
+
+/// Calculates the radius at which the focus appears at the same size with a different field of view.
+///
+/// Converts between zooming by radius and zooming by field of view without a visual jump. The size is kept
+/// exactly on the focus plane, which is a close approximation for objects small compared to the radius.
+///
+/// # Parameters
+/// * `radius` - The current radius.
+/// * `fov` - The current vertical field of view in radians.
+/// * `target_fov` - The vertical field of view in radians to calculate the radius for.
+pub fn equivalent_zoom_radius(radius: f32, fov: f32, target_fov: f32) -> f32 {
+    radius * (0.5 * fov).tan() / (0.5 * target_fov).tan()
+}
+
+/// Calculates the position of the focus in the viewport, e.g. for drawing a focus crosshair.
+///
+/// # Parameters
+/// * `camera` - The camera instance to project with.
+/// * `camera_transform` - The global transform of the camera.
+/// * `orbit` - The `OrbitCamera` properties providing the focus.
+///
+/// # Returns
+/// Returns the logical viewport position of the focus with the origin at the top left,
+/// or `None` if the focus is behind the camera or outside the clip range.
+pub fn focus_to_screen(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    orbit: &OrbitCamera,
+) -> Option<Vec2> {
+    camera.world_to_viewport(camera_transform, orbit.focus)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{test_camera, VIEWPORT_SIZE};
+
+    #[test]
+    fn orthographic_pan_scaling_follows_a_zoom_of_the_same_frame() {
+        let camera = test_camera();
+        // the area of the projection is stale until bevy updates it after `update_transform`
+        let projection = Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical(2.0),
+            ..default()
+        });
+        let mut orbit = OrbitCamera::default();
+        let before = calculate_pan_scaling_factor(&camera, &projection, &orbit).unwrap();
+        orbit.zoom(0.5);
+        let after = calculate_pan_scaling_factor(&camera, &projection, &orbit).unwrap();
+        assert!((after.y - 0.5 * before.y).abs() < 1e-7);
+        // dragging across the viewport pans by the height of the view at the new scale
+        let pan = after.y * VIEWPORT_SIZE.y as f32;
+        assert!((pan - 2.0 * orbit.radius).abs() < 1e-5);
+    }
+
+    #[test]
+    fn cursor_ray_needs_the_logical_viewport_size() {
+        let orbit = OrbitCamera::default();
+        let ray = cursor_ray(
+            &orbit,
+            &Transform::default(),
+            &Camera::default(),
+            &Projection::default(),
+            Vec2::ZERO,
+        );
+        assert!(ray.is_none());
+    }
+
+    #[test]
+    fn center_ray_starts_at_the_near_plane_and_passes_through_the_focus() {
+        let mut orbit = OrbitCamera::new(Vec3::new(1.0, 2.0, 3.0), 5.0).with_orbit(0.6, 0.3, 0.0);
+        let mut transform = Transform::default();
+        let mut projection = Projection::default();
+        orbit.update_transform(&mut transform, &mut projection);
+
+        let ray = ndc_ray(&transform, &projection, Vec2::ZERO).unwrap();
+        let forward: Vec3 = transform.forward().into();
+        assert!(Vec3::from(ray.direction).abs_diff_eq(forward, 1e-4));
+        let near = match &projection {
+            Projection::Perspective(p) => p.near,
+            Projection::Orthographic(_) => unreachable!(),
+        };
+        assert!(ray
+            .origin
+            .abs_diff_eq(transform.translation + forward * near, 1e-3));
+        let to_focus = orbit.focus - ray.origin;
+        assert!(to_focus.normalize().abs_diff_eq(forward, 1e-4));
+    }
+
+    #[test]
+    fn straight_down_camera_has_a_square_footprint() {
+        let transform = Transform::from_xyz(2.0, 10.0, -3.0).looking_to(Vec3::NEG_Y, Vec3::NEG_Z);
+        let projection = Projection::default();
+        let footprint =
+            footprint_for_viewport(&projection, &transform, Vec2::splat(600.0), 0.0, UpAxis::Y)
+                .unwrap();
+        let half_extent = match &projection {
+            Projection::Perspective(p) => 10.0 * (0.5 * p.fov).tan(),
+            Projection::Orthographic(_) => unreachable!(),
+        };
+        for corner in footprint {
+            let offset = (corner - Vec2::new(2.0, -3.0)).abs();
+            assert!(offset.abs_diff_eq(Vec2::splat(half_extent), 1e-3));
+        }
+    }
+
+    #[test]
+    fn tilted_camera_has_a_trapezoid_footprint() {
+        let transform = Transform::from_xyz(0.0, 10.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y);
+        let [bottom_left, bottom_right, top_right, top_left] = footprint_for_viewport(
+            &Projection::default(),
+            &transform,
+            Vec2::new(800.0, 600.0),
+            0.0,
+            UpAxis::Y,
+        )
+        .unwrap();
+        // the near and far edges are parallel, the far one further from the camera and wider
+        assert!((bottom_left.y - bottom_right.y).abs() < 1e-3);
+        assert!((top_left.y - top_right.y).abs() < 1e-3);
+        assert!(top_left.y < bottom_left.y);
+        assert!(top_right.x - top_left.x > bottom_right.x - bottom_left.x);
+    }
+
+    #[test]
+    fn camera_looking_away_from_the_ground_has_no_footprint() {
+        let transform = Transform::from_xyz(0.0, 10.0, 0.0).looking_to(Vec3::Y, Vec3::Z);
+        let footprint = footprint_for_viewport(
+            &Projection::default(),
+            &transform,
+            Vec2::new(800.0, 600.0),
+            0.0,
+            UpAxis::Y,
+        );
+        assert!(footprint.is_none());
+    }
+
+    #[test]
+    fn arcball_is_centered_on_an_offset_viewport() {
+        // the right half of a 1600x600 window
+        let viewport = Rect::new(800.0, 0.0, 1600.0, 600.0);
+        assert!(arcball_vector(Vec2::new(1200.0, 300.0), viewport).abs_diff_eq(Vec3::Z, 1e-6));
+        assert!(!arcball_vector(Vec2::new(800.0, 300.0), viewport).abs_diff_eq(Vec3::Z, 1e-3));
+
+        // points mirrored about the viewport center map to mirrored vectors
+        let right = arcball_vector(Vec2::new(1300.0, 250.0), viewport);
+        let left = arcball_vector(Vec2::new(1100.0, 350.0), viewport);
+        assert!(right.abs_diff_eq(Vec3::new(-left.x, -left.y, left.z), 1e-6));
+        // window coordinates point down, so the upper point is up in view space
+        assert!(right.x > 0.0 && right.y > 0.0);
+    }
+
+    #[test]
+    fn framing_radius_fits_the_sphere_in_the_field_of_view() {
+        assert!(
+            calculate_framing_radius(&Camera::default(), &Projection::default(), 1.0).is_none()
+        );
+
+        let fov = PerspectiveProjection::default().fov;
+        let projection = Projection::default();
+        // the height is the smaller dimension of a landscape viewport, and spans the vertical field of view
+        let landscape = OrbitCamera::distance_for_screen_fraction(
+            2.0,
+            1.0,
+            &projection,
+            Vec2::new(800.0, 600.0),
+        );
+        assert!(((2.0 / landscape).asin() - 0.5 * fov).abs() < 1e-5);
+        // the width of a portrait viewport spans less than the vertical field of view, so the sphere is framed from further
+        let portrait = OrbitCamera::distance_for_screen_fraction(
+            2.0,
+            1.0,
+            &projection,
+            Vec2::new(300.0, 600.0),
+        );
+        let half_width_angle = ((0.5 * fov).tan() * 0.5).atan();
+        assert!(((2.0 / portrait).asin() - half_width_angle).abs() < 1e-5);
+        assert!(portrait > landscape);
+    }
+
+    #[test]
+    fn reflection_mirrors_position_and_view_across_the_plane() {
+        let transform =
+            Transform::from_xyz(1.0, 3.0, 4.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y);
+        let reflected = reflect_transform(&transform, Vec3::ZERO, Vec3::Y);
+        let mirror = Vec3::new(1.0, -1.0, 1.0);
+        assert!(reflected
+            .translation
+            .abs_diff_eq(Vec3::new(1.0, -3.0, 4.0), 1e-5));
+        let forward: Vec3 = transform.forward().into();
+        let up: Vec3 = transform.up().into();
+        assert!(Vec3::from(reflected.forward()).abs_diff_eq(forward * mirror, 1e-5));
+        assert!(Vec3::from(reflected.up()).abs_diff_eq(up * mirror, 1e-5));
+        // the reflected camera looks at the mirrored target
+        let to_target = (Vec3::new(0.0, -1.0, 0.0) - reflected.translation).normalize();
+        assert!(Vec3::from(reflected.forward()).abs_diff_eq(to_target, 1e-5));
+        assert!(reflected.rotation.is_normalized());
+    }
+
+    #[test]
+    fn degenerate_projections_have_no_pan_scaling_factor() {
+        let camera = test_camera();
+        let property = OrbitCamera::new(Vec3::ZERO, 5.0);
+        let zero_fov = Projection::Perspective(PerspectiveProjection {
+            fov: 0.0,
+            ..default()
+        });
+        assert_eq!(
+            calculate_pan_scaling_factor(&camera, &zero_fov, &property),
+            None
+        );
+        let zero_area = Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: 0.0,
+                height: 0.0,
+            },
+            ..default()
+        });
+        assert_eq!(
+            calculate_pan_scaling_factor(&camera, &zero_area, &property),
+            None
+        );
+
+        let factor =
+            calculate_pan_scaling_factor(&camera, &Projection::default(), &property).unwrap();
+        assert!(factor.is_finite() && factor.cmpgt(Vec2::ZERO).all());
+    }
+
+    #[test]
+    fn focus_to_screen_needs_the_computed_camera() {
+        // the projection matrix and logical viewport size are computed by bevy's camera system,
+        // so even a known physical viewport gives no position before the camera has been rendered
+        let orbit = OrbitCamera::new(Vec3::ZERO, 5.0);
+        let transform = GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 5.0));
+        assert_eq!(focus_to_screen(&test_camera(), &transform, &orbit), None);
+    }
+}